@@ -0,0 +1,83 @@
+pub mod model;
+pub mod planner;
+
+use model::FuelModel;
+
+pub struct ModuleMasses {
+    pub masses: Vec<u64>,
+}
+
+pub fn parse_input(contents: &str) -> ModuleMasses {
+    let masses: Vec<u64> = contents
+        .lines()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse::<u64>().unwrap())
+        .collect();
+    ModuleMasses { masses }
+}
+
+pub fn part1(input: &ModuleMasses) -> u128 {
+    let mut total = 0;
+    for mass in &input.masses {
+        total += calculate_fuel(*mass) as u128;
+    }
+    total
+}
+
+pub fn part2(input: &ModuleMasses) -> u128 {
+    let mut total = 0;
+    for mass in &input.masses {
+        total += calculate_all_the_fuel(*mass);
+    }
+    total
+}
+
+pub fn calculate_fuel(mass: u64) -> u64 {
+    FuelModel::default().fuel(mass as u128) as u64
+}
+
+pub fn calculate_all_the_fuel(mass: u64) -> u128 {
+    FuelModel::default().total_fuel(mass as u128)
+}
+
+#[cfg(test)]
+mod test {
+    use super::calculate_all_the_fuel;
+    use super::calculate_fuel;
+
+    #[test]
+    fn part1_first_sample_input() {
+        assert_eq!(calculate_fuel(12), 2);
+    }
+
+    #[test]
+    fn part1_second_sample_input() {
+        assert_eq!(calculate_fuel(14), 2);
+    }
+
+    #[test]
+    fn part1_third_sample_input() {
+        assert_eq!(calculate_fuel(1969), 654);
+    }
+
+    #[test]
+    fn part1_fourth_sample_input() {
+        assert_eq!(calculate_fuel(100756), 33583);
+    }
+
+    #[test]
+    fn part2_first_sample_input() {
+        assert_eq!(calculate_all_the_fuel(14), 2);
+    }
+
+    #[test]
+    fn part2_second_sample_input() {
+        assert_eq!(calculate_all_the_fuel(1969), 966);
+    }
+
+    #[test]
+    fn part2_third_sample_input() {
+        assert_eq!(calculate_all_the_fuel(100756), 50346);
+    }
+}
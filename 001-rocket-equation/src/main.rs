@@ -1,76 +1,71 @@
-use std::fs::File;
-use std::io::prelude::*;
+use aoc_bench::{input_source, read_input, Bench};
+use rocket_equation::model::{stages, FuelModel, Rounding};
+use rocket_equation::planner::{parse_manifest, plan, report, stages_report, ManifestFormat};
+use rocket_equation::{parse_input, part1, part2};
+use std::env;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Failed to read input file");
-    let module_masses: Vec<i32> = contents
-        .split_terminator('\n')
-        .map(|v| v.parse::<i32>().unwrap())
-        .collect();
-    let mut total1 = 0;
-    let mut total2 = 0;
-    for mass in module_masses {
-        total1 = total1 + calculate_fuel(mass);
-        total2 = total2 + calculate_all_the_fuel(mass);
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "bench" {
+        let mut bench = Bench::from_args(&args[2..]);
+        let contents = read_input(&bench.input);
+        let input = parse_input(&contents);
+        bench.time("parse", || parse_input(&contents));
+        bench.time("part1", || part1(&input));
+        bench.time("part2", || part2(&input));
+        bench.finish();
+        return;
     }
 
-    println!("Rocket Equasion part1 Solution: {}", total1);
+    // plan [manifest] [--divisor n] [--subtract n] [--rounding down|up|nearest]
+    // [--stages dry1,dry2,..] prints the fuel breakdown of a CSV, JSON or
+    // mass list, and of the stages lifting it all
+    if args.len() > 1 && args[1] == "plan" {
+        let mut source = input_source(None);
+        let mut model = FuelModel::default();
+        let mut dry_masses = Vec::new();
+        let mut options = args[2..].iter();
+        while let Some(option) = options.next() {
+            let mut value = || {
+                options
+                    .next()
+                    .unwrap_or_else(|| panic!("Missing value for {}", option))
+            };
+            match option.as_str() {
+                "--divisor" => model.divisor = value().parse().expect("Invalid divisor"),
+                "--subtract" => model.subtraction = value().parse().expect("Invalid subtraction"),
+                "--rounding" => {
+                    model.rounding = Rounding::parse(value()).expect("Invalid rounding")
+                }
+                "--stages" => {
+                    dry_masses = value()
+                        .split(',')
+                        .map(|m| m.trim().parse().expect("Invalid stage dry mass"))
+                        .collect()
+                }
+                _ => source = option.to_string(),
+            }
+        }
+        let model = FuelModel::new(model.divisor, model.subtraction, model.rounding);
 
-    println!("Rocket Equasion part2 Solution: {}", total2);
-}
-
-fn calculate_fuel(mass: i32) -> i32 {
-    mass / 3 - 2
-}
-
-fn calculate_all_the_fuel(mass: i32) -> i32 {
-    let value = calculate_fuel(mass);
-    if value > 0 {
-        return value + calculate_all_the_fuel(value);
+        let contents = read_input(&source);
+        let format = ManifestFormat::detect(&source, &contents);
+        let modules = parse_manifest(&contents, format)
+            .unwrap_or_else(|e| panic!("Invalid manifest {}: {}", source, e));
+        let plan = plan(&modules, &model);
+        println!("{}", report(&plan));
+        if !dry_masses.is_empty() {
+            let payload = plan.total.mass + plan.total.total();
+            println!("\n{}", stages_report(&stages(&model, payload, &dry_masses)));
+        }
+        return;
     }
-    return 0;
-}
 
-#[cfg(test)]
-mod test {
-    use super::calculate_all_the_fuel;
-    use super::calculate_fuel;
+    let source = input_source(env::args().nth(1));
+    let contents = read_input(&source);
+    let module_masses = parse_input(&contents);
 
-    #[test]
-    fn part1_first_sample_input() {
-        assert_eq!(calculate_fuel(12), 2);
-    }
-
-    #[test]
-    fn part1_second_sample_input() {
-        assert_eq!(calculate_fuel(14), 2);
-    }
+    println!("Rocket Equasion part1 Solution: {}", part1(&module_masses));
 
-    #[test]
-    fn part1_third_sample_input() {
-        assert_eq!(calculate_fuel(1969), 654);
-    }
-
-    #[test]
-    fn part1_fourth_sample_input() {
-        assert_eq!(calculate_fuel(100756), 33583);
-    }
-
-    #[test]
-    fn part2_first_sample_input() {
-        assert_eq!(calculate_all_the_fuel(14), 2);
-    }
-
-    #[test]
-    fn part2_second_sample_input() {
-        assert_eq!(calculate_all_the_fuel(1969), 966);
-    }
-
-    #[test]
-    fn part2_third_sample_input() {
-        assert_eq!(calculate_all_the_fuel(100756), 50346);
-    }
+    println!("Rocket Equasion part2 Solution: {}", part2(&module_masses));
 }
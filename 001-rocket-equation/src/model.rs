@@ -0,0 +1,151 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+    Nearest,
+}
+impl Rounding {
+    pub fn parse(name: &str) -> Option<Rounding> {
+        match name {
+            "down" => Some(Rounding::Down),
+            "up" => Some(Rounding::Up),
+            "nearest" => Some(Rounding::Nearest),
+            _ => None,
+        }
+    }
+}
+
+// fuel = round(mass / divisor) - subtraction, never below zero
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FuelModel {
+    pub divisor: u64,
+    pub subtraction: u64,
+    pub rounding: Rounding,
+}
+impl FuelModel {
+    pub fn new(divisor: u64, subtraction: u64, rounding: Rounding) -> FuelModel {
+        assert!(divisor >= 2, "Divisor has to be at least 2");
+        FuelModel {
+            divisor,
+            subtraction,
+            rounding,
+        }
+    }
+
+    pub fn fuel(&self, mass: u128) -> u128 {
+        let divisor = self.divisor as u128;
+        let quotient = match self.rounding {
+            Rounding::Down => mass / divisor,
+            Rounding::Up => mass.div_ceil(divisor),
+            Rounding::Nearest => mass / divisor + (mass % divisor * 2 >= divisor) as u128,
+        };
+        quotient.saturating_sub(self.subtraction as u128)
+    }
+
+    // fuel for the mass plus the fuel for that fuel, until no more is needed
+    pub fn total_fuel(&self, mass: u128) -> u128 {
+        let mut total = 0;
+        let mut curr = mass;
+        loop {
+            let value = self.fuel(curr);
+            total += value;
+            // without a subtraction a single unit of fuel would need itself
+            // forever when rounding up, so it's only carried once
+            if value == 0 || value >= curr {
+                break;
+            }
+            curr = value;
+        }
+        total
+    }
+}
+impl Default for FuelModel {
+    // the puzzle's mass / 3 - 2
+    fn default() -> FuelModel {
+        FuelModel::new(3, 2, Rounding::Down)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageFuel {
+    pub payload: u128,
+    pub dry_mass: u64,
+    pub fuel: u128,
+    pub fuel_for_fuel: u128,
+}
+impl StageFuel {
+    pub fn total_mass(&self) -> u128 {
+        self.payload + self.dry_mass as u128 + self.fuel + self.fuel_for_fuel
+    }
+}
+
+// each stage lifts its own dry mass and everything above it, so the whole
+// stage N, fuel included, becomes part of the payload of stage N + 1
+pub fn stages(model: &FuelModel, payload: u128, dry_masses: &[u64]) -> Vec<StageFuel> {
+    let mut res: Vec<StageFuel> = Vec::new();
+    let mut payload = payload;
+    for &dry_mass in dry_masses {
+        let mass = payload + dry_mass as u128;
+        let fuel = model.fuel(mass);
+        let stage = StageFuel {
+            payload,
+            dry_mass,
+            fuel,
+            fuel_for_fuel: model.total_fuel(mass) - fuel,
+        };
+        payload = stage.total_mass();
+        res.push(stage);
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::{stages, FuelModel, Rounding};
+
+    #[test]
+    fn default_model_is_the_puzzle_formula() {
+        let model = FuelModel::default();
+        assert_eq!(model.fuel(12), 2);
+        assert_eq!(model.fuel(1969), 654);
+        assert_eq!(model.fuel(5), 0);
+        assert_eq!(model.total_fuel(1969), 966);
+        assert_eq!(model.total_fuel(100756), 50346);
+    }
+
+    #[test]
+    fn rounding_modes() {
+        let down = FuelModel::new(4, 1, Rounding::Down);
+        let up = FuelModel::new(4, 1, Rounding::Up);
+        let nearest = FuelModel::new(4, 1, Rounding::Nearest);
+        assert_eq!((down.fuel(17), up.fuel(17), nearest.fuel(17)), (3, 4, 3));
+        assert_eq!((down.fuel(18), up.fuel(18), nearest.fuel(18)), (3, 4, 4));
+        assert_eq!((down.fuel(20), up.fuel(20), nearest.fuel(20)), (4, 4, 4));
+    }
+
+    #[test]
+    fn rounding_up_without_subtraction_terminates() {
+        let model = FuelModel::new(3, 0, Rounding::Up);
+        // 9 => 3 => 1 => 1
+        assert_eq!(model.total_fuel(9), 3 + 1 + 1);
+    }
+
+    #[test]
+    fn stage_fuel_is_payload_of_next_stage() {
+        let model = FuelModel::default();
+        let res = stages(&model, 1000, &[100, 500]);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].fuel, model.fuel(1100));
+        assert_eq!(res[0].fuel + res[0].fuel_for_fuel, model.total_fuel(1100));
+        assert_eq!(res[1].payload, res[0].total_mass());
+        assert_eq!(
+            res[1].fuel + res[1].fuel_for_fuel,
+            model.total_fuel(res[0].total_mass() + 500)
+        );
+    }
+
+    #[test]
+    fn no_stages() {
+        assert!(stages(&FuelModel::default(), 1000, &[]).is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+use crate::model::{FuelModel, StageFuel};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Module {
+    pub name: String,
+    pub mass: u64,
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ManifestFormat {
+    Csv,
+    Json,
+    // one mass per line, like the puzzle input
+    Masses,
+}
+impl ManifestFormat {
+    pub fn detect(source: &str, contents: &str) -> ManifestFormat {
+        if source.ends_with(".json") {
+            return ManifestFormat::Json;
+        }
+        if source.ends_with(".csv") {
+            return ManifestFormat::Csv;
+        }
+        let first_line = contents.lines().find(|l| !l.trim().is_empty());
+        match first_line {
+            Some(l) if l.trim_start().starts_with('[') => ManifestFormat::Json,
+            Some(l) if l.trim().parse::<u64>().is_err() => ManifestFormat::Csv,
+            _ => ManifestFormat::Masses,
+        }
+    }
+}
+
+pub fn parse_manifest(contents: &str, format: ManifestFormat) -> Result<Vec<Module>, String> {
+    match format {
+        ManifestFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+        ManifestFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .flexible(true)
+                .from_reader(contents.as_bytes());
+            let mut modules = Vec::new();
+            for record in reader.deserialize() {
+                let mut module: Module = record.map_err(|e| e.to_string())?;
+                if module.group.as_ref().is_some_and(|g| g.is_empty()) {
+                    module.group = None;
+                }
+                modules.push(module);
+            }
+            Ok(modules)
+        }
+        ManifestFormat::Masses => contents
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                let mass = l
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|e| format!("line {}: {}", i + 1, e))?;
+                Ok(Module {
+                    name: format!("module-{}", i + 1),
+                    mass,
+                    group: None,
+                })
+            })
+            .collect(),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleFuel {
+    pub name: String,
+    pub group: Option<String>,
+    pub mass: u64,
+    // fuel for the module mass alone
+    pub fuel: u128,
+    // extra fuel needed to carry that fuel
+    pub fuel_for_fuel: u128,
+}
+impl ModuleFuel {
+    pub fn total(&self) -> u128 {
+        self.fuel + self.fuel_for_fuel
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupFuel {
+    pub group: Option<String>,
+    pub modules: usize,
+    pub mass: u128,
+    pub fuel: u128,
+    pub fuel_for_fuel: u128,
+}
+impl GroupFuel {
+    pub fn total(&self) -> u128 {
+        self.fuel + self.fuel_for_fuel
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuelPlan {
+    pub modules: Vec<ModuleFuel>,
+    // in order of the first module of each group
+    pub groups: Vec<GroupFuel>,
+    pub total: GroupFuel,
+}
+
+pub fn plan(modules: &[Module], model: &FuelModel) -> FuelPlan {
+    let modules: Vec<ModuleFuel> = modules
+        .iter()
+        .map(|m| {
+            let fuel = model.fuel(m.mass as u128);
+            ModuleFuel {
+                name: m.name.clone(),
+                group: m.group.clone(),
+                mass: m.mass,
+                fuel,
+                fuel_for_fuel: model.total_fuel(m.mass as u128) - fuel,
+            }
+        })
+        .collect();
+
+    let mut groups: Vec<GroupFuel> = Vec::new();
+    let mut total = empty_group(None);
+    for module in &modules {
+        let index = match groups.iter().position(|g| g.group == module.group) {
+            Some(index) => index,
+            None => {
+                groups.push(empty_group(module.group.clone()));
+                groups.len() - 1
+            }
+        };
+        add_to_group(&mut groups[index], module);
+        add_to_group(&mut total, module);
+    }
+
+    FuelPlan {
+        modules,
+        groups,
+        total,
+    }
+}
+
+fn empty_group(group: Option<String>) -> GroupFuel {
+    GroupFuel {
+        group,
+        modules: 0,
+        mass: 0,
+        fuel: 0,
+        fuel_for_fuel: 0,
+    }
+}
+
+fn add_to_group(group: &mut GroupFuel, module: &ModuleFuel) {
+    group.modules += 1;
+    group.mass += module.mass as u128;
+    group.fuel += module.fuel;
+    group.fuel_for_fuel += module.fuel_for_fuel;
+}
+
+pub fn report(plan: &FuelPlan) -> String {
+    let header = format!(
+        "{:<20}{:<12}{:>16}{:>16}{:>16}{:>16}",
+        "module", "group", "mass", "fuel", "fuel for fuel", "total"
+    );
+    let mut lines = vec![header];
+    for m in &plan.modules {
+        lines.push(format!(
+            "{:<20}{:<12}{:>16}{:>16}{:>16}{:>16}",
+            m.name,
+            m.group.as_deref().unwrap_or("-"),
+            m.mass,
+            m.fuel,
+            m.fuel_for_fuel,
+            m.total()
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "{:<20}{:<12}{:>16}{:>16}{:>16}{:>16}",
+        "group", "modules", "mass", "fuel", "fuel for fuel", "total"
+    ));
+    for g in plan.groups.iter().chain(Some(&plan.total)) {
+        let name = if std::ptr::eq(g, &plan.total) {
+            "all"
+        } else {
+            g.group.as_deref().unwrap_or("-")
+        };
+        lines.push(format!(
+            "{:<20}{:<12}{:>16}{:>16}{:>16}{:>16}",
+            name,
+            g.modules,
+            g.mass,
+            g.fuel,
+            g.fuel_for_fuel,
+            g.total()
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn stages_report(stages: &[StageFuel]) -> String {
+    let mut lines = vec![format!(
+        "{:<8}{:>16}{:>16}{:>16}{:>16}{:>16}",
+        "stage", "payload", "dry mass", "fuel", "fuel for fuel", "total mass"
+    )];
+    for (i, s) in stages.iter().enumerate() {
+        lines.push(format!(
+            "{:<8}{:>16}{:>16}{:>16}{:>16}{:>16}",
+            i + 1,
+            s.payload,
+            s.dry_mass,
+            s.fuel,
+            s.fuel_for_fuel,
+            s.total_mass()
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_manifest, plan, report, stages_report, ManifestFormat, Module};
+    use crate::model::{stages, FuelModel, Rounding};
+    use crate::{parse_input, part1, part2};
+
+    fn module(name: &str, mass: u64, group: Option<&str>) -> Module {
+        Module {
+            name: name.to_string(),
+            mass,
+            group: group.map(|g| g.to_string()),
+        }
+    }
+
+    #[test]
+    fn detect_format() {
+        assert_eq!(
+            ManifestFormat::detect("fleet.json", ""),
+            ManifestFormat::Json
+        );
+        assert_eq!(
+            ManifestFormat::detect("-", "name,mass\na,12\n"),
+            ManifestFormat::Csv
+        );
+        assert_eq!(
+            ManifestFormat::detect("-", "  [{\"name\": \"a\", \"mass\": 1}]"),
+            ManifestFormat::Json
+        );
+        assert_eq!(
+            ManifestFormat::detect("input.txt", "\n12\n14\n"),
+            ManifestFormat::Masses
+        );
+    }
+
+    #[test]
+    fn parse_csv_and_json() {
+        let csv = "name,mass,group\nengine,1969,core\n\ntank,100756,\n";
+        let json = r#"[
+            {"name": "engine", "mass": 1969, "group": "core"},
+            {"name": "tank", "mass": 100756}
+        ]"#;
+        let expected = vec![
+            module("engine", 1969, Some("core")),
+            module("tank", 100756, None),
+        ];
+        assert_eq!(
+            parse_manifest(csv, ManifestFormat::Csv),
+            Ok(expected.clone())
+        );
+        assert_eq!(parse_manifest(json, ManifestFormat::Json), Ok(expected));
+        assert!(parse_manifest("name,mass\nx,heavy\n", ManifestFormat::Csv).is_err());
+        assert!(parse_manifest("12\nabc\n", ManifestFormat::Masses)
+            .unwrap_err()
+            .starts_with("line 2"));
+    }
+
+    #[test]
+    fn fuel_per_module_and_group() {
+        let plan = plan(
+            &[
+                module("a", 14, Some("x")),
+                module("b", 1969, Some("y")),
+                module("c", 100756, Some("x")),
+            ],
+            &FuelModel::default(),
+        );
+        assert_eq!(plan.modules[1].fuel, 654);
+        assert_eq!(plan.modules[1].fuel_for_fuel, 966 - 654);
+        assert_eq!(plan.groups.len(), 2);
+        assert_eq!(plan.groups[0].group, Some(String::from("x")));
+        assert_eq!(plan.groups[0].modules, 2);
+        assert_eq!(plan.groups[0].fuel, 2 + 33583);
+        assert_eq!(plan.groups[0].total(), 2 + 50346);
+        assert_eq!(plan.total.total(), 2 + 966 + 50346);
+        assert!(report(&plan).contains("fuel for fuel"));
+    }
+
+    #[test]
+    fn totals_match_puzzle_answers() {
+        let contents = include_str!("../input.txt");
+        let modules = parse_manifest(contents, ManifestFormat::Masses).unwrap();
+        let plan = plan(&modules, &FuelModel::default());
+        let input = parse_input(contents);
+        assert_eq!(plan.total.fuel, part1(&input));
+        assert_eq!(plan.total.total(), part2(&input));
+    }
+
+    #[test]
+    fn large_fleet_does_not_overflow() {
+        let modules: Vec<Module> = (0..1000)
+            .map(|i| module(&i.to_string(), u64::MAX, None))
+            .collect();
+        let plan = plan(&modules, &FuelModel::default());
+        assert_eq!(plan.total.mass, u64::MAX as u128 * 1000);
+        assert!(plan.total.fuel > u64::MAX as u128);
+    }
+
+    #[test]
+    fn custom_model_and_stages() {
+        let model = FuelModel::new(2, 0, Rounding::Nearest);
+        let plan = plan(&[module("a", 9, None)], &model);
+        // 9 => 5 => 3 => 2 => 1 => 1
+        assert_eq!(plan.total.fuel, 5);
+        assert_eq!(plan.total.fuel_for_fuel, 3 + 2 + 1 + 1);
+        let stages = stages(&model, plan.total.mass + plan.total.total(), &[4, 10]);
+        assert_eq!(stages[0].payload, 21);
+        assert_eq!(stages[1].payload, stages[0].total_mass());
+        assert_eq!(stages_report(&stages).lines().count(), 3);
+    }
+}
@@ -0,0 +1,104 @@
+pub mod symbolic;
+
+#[derive(Clone, Debug)]
+pub struct Program {
+    pub memory: Vec<i32>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NounVerb {
+    pub noun: i32,
+    pub verb: i32,
+}
+
+pub fn parse_input(contents: &str) -> Program {
+    let memory: Vec<i32> = contents
+        .trim()
+        .split_terminator(',')
+        .map(|v| v.parse::<i32>().unwrap())
+        .collect();
+    Program { memory }
+}
+
+pub fn part1(program: &Program) -> i32 {
+    let mut first_list = program.memory.clone();
+    first_list[1] = 12;
+    first_list[2] = 2;
+
+    let result_list = compute(&mut first_list);
+    result_list[0]
+}
+
+pub fn part2(program: &Program) -> NounVerb {
+    let solutions = symbolic::solve(&program.memory, 19690720, 0..=99, 0..=99);
+    match solutions.first() {
+        Some(solution) => solution.clone(),
+        None => panic!("Could not find initial values for target {}", 19690720),
+    }
+}
+
+pub fn compute(int_list: &mut [i32]) -> Vec<i32> {
+    let mut pos = 0;
+    while pos < int_list.len() {
+        let opcode = int_list[pos];
+        match opcode {
+            99 => break,
+            1 => {
+                let store_index = int_list[pos + 3] as usize;
+                int_list[store_index] =
+                    int_list[int_list[pos + 1] as usize] + int_list[int_list[pos + 2] as usize];
+            }
+            2 => {
+                let store_index = int_list[pos + 3] as usize;
+                int_list[store_index] =
+                    int_list[int_list[pos + 1] as usize] * int_list[int_list[pos + 2] as usize];
+            }
+            _ => panic!("Something went wrong"),
+        }
+        pos += 4;
+    }
+    int_list.to_vec()
+}
+
+pub fn find_noun_and_verb(int_list: &mut [i32], target_val: i32) -> (i32, i32) {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            let mut list = int_list.to_owned();
+            list[1] = noun;
+            list[2] = verb;
+            let res_list = compute(&mut list);
+            if res_list[0] == target_val {
+                return (noun, verb);
+            }
+        }
+    }
+    panic!("Could not find initial values for target {}", target_val);
+}
+
+#[cfg(test)]
+mod test {
+    use super::compute;
+
+    #[test]
+    fn part1_first_sample_input() {
+        assert_eq!(compute(&mut [1, 0, 0, 0, 99]), [2, 0, 0, 0, 99]);
+    }
+
+    #[test]
+    fn part1_second_sample_input() {
+        assert_eq!(compute(&mut [2, 3, 0, 3, 99]), [2, 3, 0, 6, 99]);
+    }
+
+    #[test]
+    fn part1_third_sample_input() {
+        assert_eq!(compute(&mut [2, 4, 4, 5, 99, 0]), [2, 4, 4, 5, 99, 9801]);
+    }
+
+    #[test]
+    fn part1_fourth_sample_input() {
+        assert_eq!(
+            compute(&mut [1, 1, 1, 4, 99, 5, 6, 0, 99]),
+            [30, 1, 1, 4, 2, 5, 6, 0, 99]
+        );
+    }
+}
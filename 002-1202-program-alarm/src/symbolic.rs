@@ -0,0 +1,571 @@
+use crate::NounVerb;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial {
+    // (noun exponent, verb exponent) => coefficient, zero terms are left out
+    pub terms: BTreeMap<(u32, u32), i64>,
+}
+impl Polynomial {
+    pub fn constant(value: i64) -> Polynomial {
+        let mut terms = BTreeMap::new();
+        if value != 0 {
+            terms.insert((0, 0), value);
+        }
+        Polynomial { terms }
+    }
+
+    pub fn noun() -> Polynomial {
+        let mut terms = BTreeMap::new();
+        terms.insert((1, 0), 1);
+        Polynomial { terms }
+    }
+
+    pub fn verb() -> Polynomial {
+        let mut terms = BTreeMap::new();
+        terms.insert((0, 1), 1);
+        Polynomial { terms }
+    }
+
+    pub fn as_constant(&self) -> Option<i64> {
+        match self.terms.keys().find(|&&exponents| exponents != (0, 0)) {
+            Some(_) => None,
+            None => Some(self.coefficient(0, 0)),
+        }
+    }
+
+    pub fn coefficient(&self, noun_exp: u32, verb_exp: u32) -> i64 {
+        *self.terms.get(&(noun_exp, verb_exp)).unwrap_or(&0)
+    }
+
+    pub fn is_linear(&self) -> bool {
+        self.terms.keys().all(|&(n, v)| n + v <= 1)
+    }
+
+    pub fn add(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut terms = self.terms.clone();
+        for (exponents, coefficient) in other.terms.iter() {
+            let sum = terms
+                .get(exponents)
+                .unwrap_or(&0)
+                .checked_add(*coefficient)?;
+            terms.insert(*exponents, sum);
+        }
+        terms.retain(|_, c| *c != 0);
+        Some(Polynomial { terms })
+    }
+
+    pub fn mul(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut terms: BTreeMap<(u32, u32), i64> = BTreeMap::new();
+        for ((n1, v1), c1) in self.terms.iter() {
+            for ((n2, v2), c2) in other.terms.iter() {
+                let exponents = (n1 + n2, v1 + v2);
+                let product = c1.checked_mul(*c2)?;
+                let sum = terms.get(&exponents).unwrap_or(&0).checked_add(product)?;
+                terms.insert(exponents, sum);
+            }
+        }
+        terms.retain(|_, c| *c != 0);
+        Some(Polynomial { terms })
+    }
+
+    pub fn eval(&self, noun: i64, verb: i64) -> Option<i64> {
+        let mut res: i64 = 0;
+        for (&(n, v), &c) in self.terms.iter() {
+            let term = c
+                .checked_mul(noun.checked_pow(n)?)?
+                .checked_mul(verb.checked_pow(v)?)?;
+            res = res.checked_add(term)?;
+        }
+        Some(res)
+    }
+}
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        // highest degree first
+        let mut terms: Vec<(&(u32, u32), &i64)> = self.terms.iter().collect();
+        terms.sort_by(|((n1, v1), _), ((n2, v2), _)| (n2 + v2, n2).cmp(&(n1 + v1, n1)));
+        for (i, (&(n, v), &c)) in terms.into_iter().enumerate() {
+            if i == 0 {
+                if c < 0 {
+                    write!(f, "-")?;
+                }
+            } else if c < 0 {
+                write!(f, " - ")?;
+            } else {
+                write!(f, " + ")?;
+            }
+            let mut factors = Vec::new();
+            if c.abs() != 1 || (n == 0 && v == 0) {
+                factors.push(c.abs().to_string());
+            }
+            for &(name, exp) in &[("noun", n), ("verb", v)] {
+                match exp {
+                    0 => {}
+                    1 => factors.push(name.to_string()),
+                    _ => factors.push(format!("{}^{}", name, exp)),
+                }
+            }
+            write!(f, "{}", factors.join("*"))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Cell {
+    Known(Polynomial),
+    // a value read through an address which depends on noun or verb
+    Unknown,
+}
+
+// runs the add/mul subset with the given values in cells 1 and 2, and returns
+// the value left in cell 0, or None when the opcodes or the store addresses
+// can't be determined, or the program fails
+pub fn evaluate(memory: &[i32], noun: Polynomial, verb: Polynomial) -> Option<Polynomial> {
+    // there is nowhere to put the noun and verb
+    if memory.len() < 3 {
+        return None;
+    }
+    let mut cells: Vec<Cell> = memory
+        .iter()
+        .map(|&v| Cell::Known(Polynomial::constant(v as i64)))
+        .collect();
+    cells[1] = Cell::Known(noun);
+    cells[2] = Cell::Known(verb);
+
+    let constant_at = |cells: &Vec<Cell>, pos: usize| -> Option<usize> {
+        match cells.get(pos)? {
+            Cell::Known(p) => {
+                let value = p.as_constant()?;
+                if value >= 0 && (value as usize) < cells.len() {
+                    Some(value as usize)
+                } else {
+                    None
+                }
+            }
+            Cell::Unknown => None,
+        }
+    };
+    let mut pos = 0;
+    while pos < cells.len() {
+        let opcode = match cells.get(pos)? {
+            Cell::Known(p) => p.as_constant()?,
+            Cell::Unknown => return None,
+        };
+        match opcode {
+            99 => break,
+            1 | 2 => {
+                let store_index = constant_at(&cells, pos + 3)?;
+                let value = match (
+                    constant_at(&cells, pos + 1).map(|i| cells[i].clone()),
+                    constant_at(&cells, pos + 2).map(|i| cells[i].clone()),
+                ) {
+                    (Some(Cell::Known(a)), Some(Cell::Known(b))) => {
+                        let res = if opcode == 1 { a.add(&b) } else { a.mul(&b) };
+                        Cell::Known(res?)
+                    }
+                    _ => Cell::Unknown,
+                };
+                cells[store_index] = value;
+            }
+            _ => return None,
+        }
+        pos += 4;
+    }
+
+    match &cells[0] {
+        Cell::Known(p) => Some(p.clone()),
+        Cell::Unknown => None,
+    }
+}
+
+// every noun and verb in the given ranges which leave `target` in cell 0,
+// ordered by noun and then verb. linear polynomials are solved directly and
+// ones linear in either noun or verb are solved once per value of the other,
+// anything else, and programs that can't be evaluated symbolically, are
+// checked pair by pair
+pub fn solve(
+    memory: &[i32],
+    target: i64,
+    nouns: RangeInclusive<i32>,
+    verbs: RangeInclusive<i32>,
+) -> Vec<NounVerb> {
+    match evaluate(memory, Polynomial::noun(), Polynomial::verb()) {
+        Some(polynomial) => solve_polynomial(&polynomial, target, nouns, verbs),
+        None => solve_brute_force(memory, target, nouns, verbs),
+    }
+}
+
+pub fn solve_polynomial(
+    polynomial: &Polynomial,
+    target: i64,
+    nouns: RangeInclusive<i32>,
+    verbs: RangeInclusive<i32>,
+) -> Vec<NounVerb> {
+    let mut solutions = if polynomial.is_linear() {
+        solve_linear(
+            polynomial.coefficient(1, 0) as i128,
+            polynomial.coefficient(0, 1) as i128,
+            target as i128 - polynomial.coefficient(0, 0) as i128,
+            &nouns,
+            &verbs,
+        )
+    } else if let Some(solutions) = solve_per_noun(polynomial, target, &nouns, &verbs) {
+        solutions
+    } else if let Some(solutions) = solve_per_noun(&swapped(polynomial), target, &verbs, &nouns) {
+        solutions
+            .into_iter()
+            .map(|s| NounVerb {
+                noun: s.verb,
+                verb: s.noun,
+            })
+            .collect()
+    } else {
+        let mut solutions = Vec::new();
+        for noun in nouns {
+            for verb in verbs.clone() {
+                if polynomial.eval(noun as i64, verb as i64) == Some(target) {
+                    solutions.push(NounVerb { noun, verb });
+                }
+            }
+        }
+        solutions
+    };
+    solutions.sort_by_key(|s| (s.noun, s.verb));
+    solutions
+}
+
+// a * noun + b * verb = c
+fn solve_linear(
+    a: i128,
+    b: i128,
+    c: i128,
+    nouns: &RangeInclusive<i32>,
+    verbs: &RangeInclusive<i32>,
+) -> Vec<NounVerb> {
+    let mut solutions = Vec::new();
+    let pairs = |nouns: Vec<i32>, verbs: Vec<i32>| -> Vec<NounVerb> {
+        let mut res = Vec::new();
+        for &noun in nouns.iter() {
+            for &verb in verbs.iter() {
+                res.push(NounVerb { noun, verb });
+            }
+        }
+        res
+    };
+    let dividing = |coefficient: i128, range: &RangeInclusive<i32>| -> Vec<i32> {
+        divide_exactly(c, coefficient, range).into_iter().collect()
+    };
+    if a == 0 && b == 0 {
+        if c == 0 {
+            solutions = pairs(nouns.clone().collect(), verbs.clone().collect());
+        }
+    } else if b == 0 {
+        solutions = pairs(dividing(a, nouns), verbs.clone().collect());
+    } else if a == 0 {
+        solutions = pairs(nouns.clone().collect(), dividing(b, verbs));
+    } else {
+        let (g, x, _) = egcd(a, b);
+        if c % g != 0 {
+            return solutions;
+        }
+        // noun = noun0 + k * step_noun, verb = verb0 - k * step_verb, with
+        // noun0 taken modulo step_noun so x * (c / g) can't overflow
+        let (step_noun, step_verb) = (b / g, a / g);
+        let noun0 = (x % step_noun) * ((c / g) % step_noun) % step_noun;
+        let verb0 = (c - a * noun0) / b;
+        let (mut k_min, mut k_max) = (i128::MIN, i128::MAX);
+        for &(start, step, range) in &[(noun0, step_noun, nouns), (verb0, -step_verb, verbs)] {
+            let low = *range.start() as i128 - start;
+            let high = *range.end() as i128 - start;
+            let (from, to) = if step > 0 {
+                (div_ceil(low, step), div_floor(high, step))
+            } else {
+                (div_ceil(high, step), div_floor(low, step))
+            };
+            k_min = k_min.max(from);
+            k_max = k_max.min(to);
+        }
+        let mut k = k_min;
+        while k <= k_max {
+            let noun = i32::try_from(noun0 + k * step_noun);
+            let verb = i32::try_from(verb0 - k * step_verb);
+            if let (Ok(noun), Ok(verb)) = (noun, verb) {
+                solutions.push(NounVerb { noun, verb });
+            }
+            k += 1;
+        }
+    }
+    solutions
+}
+
+// the value in range for which coefficient * value = c, if there is one
+fn divide_exactly(c: i128, coefficient: i128, range: &RangeInclusive<i32>) -> Option<i32> {
+    if coefficient == 0 || c % coefficient != 0 {
+        return None;
+    }
+    i32::try_from(c / coefficient)
+        .ok()
+        .filter(|value| range.contains(value))
+}
+
+// when verb shows up with exponent 1 at most, every noun leaves
+// a * verb = c to solve, none otherwise
+fn solve_per_noun(
+    polynomial: &Polynomial,
+    target: i64,
+    nouns: &RangeInclusive<i32>,
+    verbs: &RangeInclusive<i32>,
+) -> Option<Vec<NounVerb>> {
+    if polynomial.terms.keys().any(|&(_, v)| v > 1) {
+        return None;
+    }
+    let (mut slope, mut rest) = (Polynomial::constant(0), Polynomial::constant(0));
+    for (&(n, v), &c) in polynomial.terms.iter() {
+        if v == 1 {
+            slope.terms.insert((n, 0), c);
+        } else {
+            rest.terms.insert((n, 0), c);
+        }
+    }
+    let mut solutions = Vec::new();
+    for noun in nouns.clone() {
+        match (slope.eval(noun as i64, 0), rest.eval(noun as i64, 0)) {
+            (Some(a), Some(b)) => {
+                let c = target as i128 - b as i128;
+                if a == 0 && c == 0 {
+                    solutions.extend(verbs.clone().map(|verb| NounVerb { noun, verb }));
+                } else if let Some(verb) = divide_exactly(c, a as i128, verbs) {
+                    solutions.push(NounVerb { noun, verb });
+                }
+            }
+            // the parts overflow on their own, so check this noun pair by pair
+            _ => {
+                for verb in verbs.clone() {
+                    if polynomial.eval(noun as i64, verb as i64) == Some(target) {
+                        solutions.push(NounVerb { noun, verb });
+                    }
+                }
+            }
+        }
+    }
+    Some(solutions)
+}
+
+fn swapped(polynomial: &Polynomial) -> Polynomial {
+    Polynomial {
+        terms: polynomial
+            .terms
+            .iter()
+            .map(|(&(n, v), &c)| ((v, n), c))
+            .collect(),
+    }
+}
+
+pub fn solve_brute_force(
+    memory: &[i32],
+    target: i64,
+    nouns: RangeInclusive<i32>,
+    verbs: RangeInclusive<i32>,
+) -> Vec<NounVerb> {
+    let mut solutions = Vec::new();
+    for noun in nouns {
+        for verb in verbs.clone() {
+            let res = evaluate(
+                memory,
+                Polynomial::constant(noun as i64),
+                Polynomial::constant(verb as i64),
+            );
+            if res == Some(Polynomial::constant(target)) {
+                solutions.push(NounVerb { noun, verb });
+            }
+        }
+    }
+    solutions
+}
+
+fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        if a < 0 {
+            (-a, -1, 0)
+        } else {
+            (a, 1, 0)
+        }
+    } else {
+        let (g, x, y) = egcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) == (b < 0)) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{evaluate, solve, solve_brute_force, Polynomial};
+    use crate::{compute, find_noun_and_verb, parse_input, NounVerb};
+
+    fn brute_force_with_compute(memory: &[i32], target: i32, max: i32) -> Vec<NounVerb> {
+        let mut solutions = Vec::new();
+        for noun in 0..=max {
+            for verb in 0..=max {
+                let mut list = memory.to_vec();
+                list[1] = noun;
+                list[2] = verb;
+                if compute(&mut list)[0] == target {
+                    solutions.push(NounVerb { noun, verb });
+                }
+            }
+        }
+        solutions
+    }
+
+    #[test]
+    fn input_is_linear_in_noun_and_verb() {
+        let program = parse_input(include_str!("../input.txt"));
+        let polynomial = evaluate(&program.memory, Polynomial::noun(), Polynomial::verb()).unwrap();
+        assert!(polynomial.is_linear());
+        assert_eq!(polynomial.eval(12, 2), Some(6568671));
+    }
+
+    #[test]
+    fn solve_matches_brute_force() {
+        let mut memory = parse_input(include_str!("../input.txt")).memory;
+        let (noun, verb) = find_noun_and_verb(&mut memory, 19690720);
+        assert_eq!(
+            solve(&memory, 19690720, 0..=99, 0..=99),
+            vec![NounVerb { noun, verb }]
+        );
+        assert_eq!(solve(&memory, 6568671, 0..=99, 0..=99).len(), 1);
+        assert_eq!(
+            solve(&memory, 6568671, 0..=99, 0..=99),
+            solve_brute_force(&memory, 6568671, 0..=99, 0..=99)
+        );
+    }
+
+    #[test]
+    fn programs_too_short_for_noun_and_verb() {
+        assert_eq!(evaluate(&[], Polynomial::noun(), Polynomial::verb()), None);
+        assert_eq!(
+            evaluate(&[99, 0], Polynomial::noun(), Polynomial::verb()),
+            None
+        );
+        assert_eq!(solve(&[99, 0], 99, 0..=5, 0..=5), vec![]);
+    }
+
+    #[test]
+    fn unreachable_target() {
+        let memory = parse_input(include_str!("../input.txt")).memory;
+        assert_eq!(solve(&memory, 1, 0..=99, 0..=99), vec![]);
+    }
+
+    #[test]
+    fn solutions_over_a_wider_range() {
+        // 2 * noun + 3 * verb = 12
+        let memory = vec![
+            1, 0, 0, 0, 1, 1, 1, 0, 1, 0, 2, 0, 1, 0, 2, 0, 1, 0, 2, 0, 99,
+        ];
+        let polynomial = evaluate(&memory, Polynomial::noun(), Polynomial::verb()).unwrap();
+        assert_eq!(polynomial.to_string(), "2*noun + 3*verb");
+        assert_eq!(
+            solve(&memory, 12, -3..=6, -3..=6),
+            vec![
+                NounVerb { noun: -3, verb: 6 },
+                NounVerb { noun: 0, verb: 4 },
+                NounVerb { noun: 3, verb: 2 },
+                NounVerb { noun: 6, verb: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn non_linear_polynomial() {
+        let memory = vec![1, 0, 0, 0, 2, 1, 2, 0, 99];
+        let polynomial = evaluate(&memory, Polynomial::noun(), Polynomial::verb()).unwrap();
+        assert_eq!(polynomial.to_string(), "noun*verb");
+        assert_eq!(
+            solve(&memory, 12, 0..=8, 0..=8),
+            brute_force_with_compute(&memory, 12, 8)
+        );
+        assert_eq!(solve(&memory, 12, 0..=8, 0..=8).len(), 4);
+    }
+
+    #[test]
+    fn targets_beyond_i32() {
+        // noun is left in cell 0
+        let memory = vec![1, 0, 0, 3, 1, 1, 9, 0, 99, 0];
+        // 2 * noun + 3 * verb = 12
+        let linear = vec![
+            1, 0, 0, 0, 1, 1, 1, 0, 1, 0, 2, 0, 1, 0, 2, 0, 1, 0, 2, 0, 99,
+        ];
+        for target in [
+            i32::MAX as i64 + 1,
+            4294967301,
+            -(i32::MAX as i64) - 10,
+            i64::MAX,
+        ] {
+            for memory in [&memory, &linear] {
+                assert_eq!(
+                    solve(memory, target, 0..=99, 0..=99),
+                    solve_brute_force(memory, target, 0..=99, 0..=99)
+                );
+            }
+        }
+        assert_eq!(solve(&memory, 4294967301, 0..=99, 0..=99), vec![]);
+        assert_eq!(solve(&memory, 5, 0..=99, 0..=99).len(), 100);
+    }
+
+    #[test]
+    fn linear_in_one_of_them() {
+        // noun * verb + verb, and verb * verb + noun
+        let per_noun = vec![1, 0, 0, 3, 2, 1, 2, 0, 1, 0, 2, 0, 99];
+        let per_verb = vec![1, 0, 0, 3, 2, 2, 2, 0, 1, 0, 1, 0, 99];
+        for memory in [&per_noun, &per_verb] {
+            let polynomial = evaluate(memory, Polynomial::noun(), Polynomial::verb()).unwrap();
+            assert!(!polynomial.is_linear());
+            assert!(!solve(memory, 12, 0..=12, 0..=12).is_empty());
+            for target in [0, 12, 30, 4294967301] {
+                assert_eq!(
+                    solve(memory, target, 0..=12, 0..=12),
+                    solve_brute_force(memory, target, 0..=12, 0..=12)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn falls_back_to_brute_force() {
+        let memory = vec![1, 0, 0, 0, 99];
+        assert_eq!(
+            evaluate(&memory, Polynomial::noun(), Polynomial::verb()),
+            None
+        );
+        assert_eq!(
+            solve(&memory, 100, 0..=4, 0..=4),
+            brute_force_with_compute(&memory, 100, 4)
+        );
+        assert_eq!(solve(&memory, 100, 0..=4, 0..=4).len(), 3);
+    }
+}
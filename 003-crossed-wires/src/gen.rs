@@ -0,0 +1,66 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const DIRECTIONS: [char; 4] = ['U', 'R', 'D', 'L'];
+
+pub fn wires(seed: u64, moves: usize, max_length: i32) -> String {
+    assert!(moves >= 2, "Each wire needs at least 2 moves");
+    assert!(max_length >= 2, "Move length has to be at least 2");
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // the first two moves of the wires always cross each other once
+    let up1 = rng.gen_range(1, max_length);
+    let right1 = rng.gen_range(2, max_length + 1);
+    let right2 = rng.gen_range(1, right1);
+    let up2 = rng.gen_range(up1 + 1, max_length + 1);
+
+    let first = random_wire(&mut rng, vec![('U', up1), ('R', right1)], moves, max_length);
+    let second = random_wire(&mut rng, vec![('R', right2), ('U', up2)], moves, max_length);
+    format!("{}\n{}\n", first, second)
+}
+
+fn random_wire(
+    rng: &mut StdRng,
+    mut path: Vec<(char, i32)>,
+    moves: usize,
+    max_length: i32,
+) -> String {
+    let (last_dir, _) = path[path.len() - 1];
+    let mut prev = DIRECTIONS.iter().position(|d| *d == last_dir).unwrap();
+    while path.len() < moves {
+        // never turn straight back over the previous move
+        let mut dir = rng.gen_range(0, 4);
+        while dir == (prev + 2) % 4 {
+            dir = rng.gen_range(0, 4);
+        }
+        path.push((DIRECTIONS[dir], rng.gen_range(1, max_length + 1)));
+        prev = dir;
+    }
+    path.iter()
+        .map(|(dir, len)| format!("{}{}", dir, len))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::wires;
+    use crate::{parse_input, part1, part2};
+
+    #[test]
+    fn same_seed_same_wires() {
+        assert_eq!(wires(7, 50, 100), wires(7, 50, 100));
+        assert_ne!(wires(7, 50, 100), wires(8, 50, 100));
+    }
+
+    #[test]
+    fn generated_wires_intersect() {
+        for seed in 0..20 {
+            let input = parse_input(&wires(seed, 30, 50));
+            assert_eq!(input.wires[0].len(), 30);
+            assert_eq!(input.wires[1].len(), 30);
+            assert!(part1(&input) < i32::MAX);
+            assert!(part2(&input) < i32::MAX);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::sweep::{fewest_steps, nearest, Crossing, Segment};
+use crate::Point;
+
+const COLOURS: [&str; 8] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#9467bd", "#ff7f0e", "#17becf", "#8c564b", "#e377c2",
+];
+
+// every crossing of all the wires, with the ones parts 1 and 2 pick marked
+pub fn report(crossings: &[Crossing], wires: usize) -> String {
+    let all_wires: Vec<usize> = (0..wires).collect();
+    let nearest = nearest(crossings, &all_wires);
+    let fewest = fewest_steps(crossings, &all_wires);
+
+    let mut header = format!("{:>10}{:>10}{:>10}", "x", "y", "distance");
+    for wire in 0..wires {
+        header.push_str(&format!("{:>10}", format!("wire {}", wire)));
+    }
+    header.push_str(&format!("{:>10}", "combined"));
+    let mut lines = vec![header];
+    for c in crossings {
+        let mut line = format!("{:>10}{:>10}{:>10}", c.point.x, c.point.y, c.distance());
+        for wire in 0..wires {
+            match c.steps.get(&wire) {
+                Some(steps) => line.push_str(&format!("{:>10}", steps)),
+                None => line.push_str(&format!("{:>10}", "-")),
+            }
+        }
+        match c.combined_steps(&all_wires) {
+            Some(steps) => line.push_str(&format!("{:>10}", steps)),
+            None => line.push_str(&format!("{:>10}", "-")),
+        }
+        if nearest == Some(c) {
+            line.push_str("  nearest");
+        }
+        if fewest == Some(c) {
+            line.push_str("  fewest steps");
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+// points the wire passes through from the origin to its end
+pub fn wire_points(segments: &[Segment]) -> Vec<Point> {
+    let mut points = vec![Point::new(0, 0)];
+    for s in segments {
+        points.push(s.end.clone());
+    }
+    points
+}
+
+pub fn svg(wires: &[Vec<Segment>], crossings: &[Crossing]) -> String {
+    let all_wires: Vec<usize> = (0..wires.len()).collect();
+    let nearest = nearest(crossings, &all_wires);
+    let fewest = fewest_steps(crossings, &all_wires);
+    let paths: Vec<Vec<Point>> = wires.iter().map(|w| wire_points(w)).collect();
+
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (0, 0, 0, 0);
+    for p in paths.iter().flatten() {
+        min_x = min_x.min(p.x);
+        max_x = max_x.max(p.x);
+        min_y = min_y.min(p.y);
+        max_y = max_y.max(p.y);
+    }
+    let extent = (max_x - min_x).max(max_y - min_y).max(1);
+    let padding = extent / 50 + 1;
+    let radius = extent / 200 + 1;
+
+    // svg y grows downwards, so wires are drawn with y flipped to keep up as up
+    let mut lines = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        min_x - padding,
+        -max_y - padding,
+        max_x - min_x + 2 * padding,
+        max_y - min_y + 2 * padding
+    )];
+    lines.push(format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"100%\" height=\"100%\" fill=\"white\"/>",
+        min_x - padding,
+        -max_y - padding
+    ));
+    for (wire, points) in paths.iter().enumerate() {
+        let coords: Vec<String> = points.iter().map(|p| format!("{},{}", p.x, -p.y)).collect();
+        lines.push(format!(
+            "<polyline id=\"wire-{}\" points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\" vector-effect=\"non-scaling-stroke\"/>",
+            wire,
+            coords.join(" "),
+            COLOURS[wire % COLOURS.len()]
+        ));
+    }
+    for c in crossings {
+        let (fill, r) = if nearest == Some(c) || fewest == Some(c) {
+            ("gold", radius * 3)
+        } else {
+            ("black", radius)
+        };
+        let steps: Vec<String> = c
+            .steps
+            .iter()
+            .map(|(wire, steps)| format!("wire {}: {}", wire, steps))
+            .collect();
+        lines.push(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"black\" vector-effect=\"non-scaling-stroke\"><title>({}, {}) distance {}, {}</title></circle>",
+            c.point.x,
+            -c.point.y,
+            r,
+            fill,
+            c.point.x,
+            c.point.y,
+            c.distance(),
+            steps.join(", ")
+        ));
+    }
+    lines.push(format!(
+        "<circle cx=\"0\" cy=\"0\" r=\"{}\" fill=\"none\" stroke=\"black\" vector-effect=\"non-scaling-stroke\"><title>origin</title></circle>",
+        radius * 3
+    ));
+    lines.push(String::from("</svg>"));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{report, svg, wire_points};
+    use crate::sweep::{find_crossings, trace};
+    use crate::{parse_input, trace_wires, Point};
+
+    #[test]
+    fn report_lists_every_crossing() {
+        let input = parse_input("R8,U5,L5,D3\nU7,R6,D4,L4\n");
+        let crossings = find_crossings(&trace_wires(&input));
+        let report = report(&crossings, 2);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("wire 1"));
+        // (3, 3) is the nearest, (6, 5) takes the fewest steps
+        assert!(lines[1]
+            .starts_with("         3         3         6        20        20        40  nearest"));
+        assert!(lines[2].ends_with("30  fewest steps"));
+    }
+
+    #[test]
+    fn report_with_wires_missing_a_crossing() {
+        let input = parse_input("R5\nU2,R2,D4\nU3,R9\n");
+        let crossings = find_crossings(&trace_wires(&input));
+        let report = report(&crossings, 3);
+        // wires 1 and 2 overlap going up, wire 0 only crosses wire 1
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[3].ends_with("         2         6         -         -"));
+        assert!(!report.contains("nearest"));
+    }
+
+    #[test]
+    fn wire_points_follow_the_moves() {
+        let moves: Vec<String> = ["R8", "U5", "L5"].iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            wire_points(&trace(0, &moves)),
+            vec![
+                Point::new(0, 0),
+                Point::new(8, 0),
+                Point::new(8, 5),
+                Point::new(3, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn svg_draws_wires_and_crossings() {
+        let input = parse_input("R8,U5,L5,D3\nU7,R6,D4,L4\n");
+        let wires = trace_wires(&input);
+        let crossings = find_crossings(&wires);
+        let svg = svg(&wires, &crossings);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        assert!(svg.contains("stroke=\"#1f77b4\""));
+        assert!(svg.contains("stroke=\"#d62728\""));
+        // two crossings and the origin
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains("(6, 5) distance 11, wire 0: 15, wire 1: 15"));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{calculate_next_position, Point};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub wire: usize,
+    pub start: Point,
+    pub end: Point,
+    // steps the wire took before reaching `start`
+    pub steps: i32,
+}
+impl Segment {
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn steps_to(&self, point: &Point) -> i32 {
+        self.steps + (point.x - self.start.x).abs() + (point.y - self.start.y).abs()
+    }
+}
+
+// every point at least two wires pass through, except the origin
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crossing {
+    pub point: Point,
+    // wire index => fewest steps the wire takes to reach the point
+    pub steps: BTreeMap<usize, i32>,
+}
+impl Crossing {
+    pub fn distance(&self) -> i32 {
+        self.point.x.abs() + self.point.y.abs()
+    }
+
+    pub fn has_wires(&self, wires: &[usize]) -> bool {
+        wires.iter().all(|w| self.steps.contains_key(w))
+    }
+
+    pub fn combined_steps(&self, wires: &[usize]) -> Option<i32> {
+        wires.iter().map(|w| self.steps.get(w)).sum()
+    }
+}
+
+pub fn trace(wire: usize, moves: &[String]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut curr = Point::new(0, 0);
+    let mut steps = 0;
+    for next_move in moves {
+        let next = calculate_next_position(next_move, curr.clone());
+        segments.push(Segment {
+            wire,
+            start: curr,
+            end: next.point.clone(),
+            steps,
+        });
+        steps += next.steps;
+        curr = next.point;
+    }
+    segments
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    // ordered so segments touching at an x coordinate still meet
+    Insert,
+    Query,
+    Remove,
+}
+
+// sweeps a vertical line from left to right keeping the horizontal segments
+// it currently crosses ordered by y, so every vertical segment only looks at
+// the horizontal ones within its own y range, O((n + k) log n) in total
+pub fn find_crossings(wires: &[Vec<Segment>]) -> Vec<Crossing> {
+    let segments: Vec<&Segment> = wires.iter().flatten().collect();
+    let mut found: HashMap<Point, BTreeMap<usize, i32>> = HashMap::new();
+    let mut record = |a: &Segment, b: &Segment, point: Point| {
+        if a.wire == b.wire || (point.x == 0 && point.y == 0) {
+            return;
+        }
+        let steps = found.entry(point.clone()).or_default();
+        for s in [a, b].iter() {
+            let to_point = s.steps_to(&point);
+            let best = steps.entry(s.wire).or_insert(to_point);
+            if *best > to_point {
+                *best = to_point;
+            }
+        }
+    };
+
+    let mut events: Vec<(i32, Event, usize)> = Vec::new();
+    for (i, s) in segments.iter().enumerate() {
+        let (low, high) = ordered(s.start.x, s.end.x);
+        if s.is_horizontal() {
+            events.push((low, Event::Insert, i));
+            events.push((high, Event::Remove, i));
+        } else {
+            events.push((low, Event::Query, i));
+        }
+    }
+    events.sort();
+    let mut active: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+    for (x, event, i) in events {
+        let y = segments[i].start.y;
+        match event {
+            Event::Insert => active.entry(y).or_default().push(i),
+            Event::Remove => active.get_mut(&y).unwrap().retain(|&j| j != i),
+            Event::Query => {
+                let (low, high) = ordered(segments[i].start.y, segments[i].end.y);
+                for (&y, horizontal) in active.range(low..=high) {
+                    for &j in horizontal {
+                        record(segments[i], segments[j], Point::new(x, y));
+                    }
+                }
+            }
+        }
+    }
+
+    // segments lying on the same line can overlap instead of crossing
+    let mut lines: HashMap<(bool, i32), Vec<&Segment>> = HashMap::new();
+    for s in segments.iter() {
+        let line = if s.is_horizontal() {
+            s.start.y
+        } else {
+            s.start.x
+        };
+        lines.entry((s.is_horizontal(), line)).or_default().push(s);
+    }
+    for ((horizontal, line), mut on_line) in lines {
+        let span = |s: &Segment| {
+            if horizontal {
+                ordered(s.start.x, s.end.x)
+            } else {
+                ordered(s.start.y, s.end.y)
+            }
+        };
+        on_line.sort_by_key(|s| span(s));
+        let mut open: Vec<&Segment> = Vec::new();
+        for s in on_line {
+            let (low, high) = span(s);
+            open.retain(|o| span(o).1 >= low);
+            for o in open.iter() {
+                for v in low..=high.min(span(o).1) {
+                    let point = if horizontal {
+                        Point::new(v, line)
+                    } else {
+                        Point::new(line, v)
+                    };
+                    record(s, o, point);
+                }
+            }
+            open.push(s);
+        }
+    }
+
+    let mut crossings: Vec<Crossing> = found
+        .into_iter()
+        .filter(|(_, steps)| steps.len() > 1)
+        .map(|(point, steps)| Crossing { point, steps })
+        .collect();
+    crossings.sort_by(|a, b| a.point.cmp(&b.point));
+    crossings
+}
+
+fn ordered(a: i32, b: i32) -> (i32, i32) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+// closest crossing to the origin among those all the given wires pass through
+pub fn nearest<'a>(crossings: &'a [Crossing], wires: &[usize]) -> Option<&'a Crossing> {
+    crossings
+        .iter()
+        .filter(|c| c.has_wires(wires))
+        .min_by_key(|c| c.distance())
+}
+
+pub fn fewest_steps<'a>(crossings: &'a [Crossing], wires: &[usize]) -> Option<&'a Crossing> {
+    crossings
+        .iter()
+        .filter(|c| c.has_wires(wires))
+        .min_by_key(|c| c.combined_steps(wires))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fewest_steps, find_crossings, nearest, trace, Segment};
+    use crate::{gen, parse_input, Point};
+    use std::collections::{BTreeMap, HashMap};
+
+    fn wires(input: &[&str]) -> Vec<Vec<Segment>> {
+        input
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                let moves: Vec<String> = w.split(',').map(|m| m.to_string()).collect();
+                trace(i, &moves)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sample_inputs() {
+        for (input, expected) in [
+            (vec!["R8,U5,L5,D3", "U7,R6,D4,L4"], (6, 30)),
+            (
+                vec![
+                    "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+                    "U62,R66,U55,R34,D71,R55,D58,R83",
+                ],
+                (159, 610),
+            ),
+        ] {
+            let crossings = find_crossings(&wires(&input));
+            assert_eq!(nearest(&crossings, &[0, 1]).unwrap().distance(), expected.0);
+            assert_eq!(
+                fewest_steps(&crossings, &[0, 1])
+                    .unwrap()
+                    .combined_steps(&[0, 1]),
+                Some(expected.1)
+            );
+        }
+    }
+
+    #[test]
+    fn collinear_overlap() {
+        let crossings = find_crossings(&wires(&["R10", "U1,R3,D1,R4,L2"]));
+        let points: Vec<Point> = crossings.iter().map(|c| c.point.clone()).collect();
+        assert_eq!(
+            points,
+            vec![
+                Point::new(3, 0),
+                Point::new(4, 0),
+                Point::new(5, 0),
+                Point::new(6, 0),
+                Point::new(7, 0)
+            ]
+        );
+        // the second wire passes (5, 0) on its way right and back left
+        assert_eq!(crossings[2].steps[&1], 1 + 3 + 1 + 2);
+    }
+
+    #[test]
+    fn three_wires() {
+        let crossings = find_crossings(&wires(&["R5", "U2,R2,D4", "D1,R2,U3"]));
+        let all = nearest(&crossings, &[0, 1, 2]).unwrap();
+        assert_eq!(all.point, Point::new(2, 0));
+        assert_eq!(all.combined_steps(&[0, 1, 2]), Some(2 + 6 + 4));
+        assert_eq!(
+            nearest(&crossings, &[1, 2]).unwrap().point,
+            Point::new(2, 0)
+        );
+        assert_eq!(crossings.len(), 4);
+    }
+
+    // walks every wire one step at a time
+    fn brute_force(wires: &[Vec<Segment>]) -> HashMap<Point, BTreeMap<usize, i32>> {
+        let mut visited: HashMap<Point, BTreeMap<usize, i32>> = HashMap::new();
+        for segments in wires {
+            for s in segments {
+                let length = (s.end.x - s.start.x).abs() + (s.end.y - s.start.y).abs();
+                let (dx, dy) = (
+                    (s.end.x - s.start.x).signum(),
+                    (s.end.y - s.start.y).signum(),
+                );
+                for i in 0..=length {
+                    let point = Point::new(s.start.x + i * dx, s.start.y + i * dy);
+                    let steps = visited.entry(point).or_default();
+                    let best = steps.entry(s.wire).or_insert(s.steps + i);
+                    *best = (*best).min(s.steps + i);
+                }
+            }
+        }
+        visited.remove(&Point::new(0, 0));
+        visited.retain(|_, steps| steps.len() > 1);
+        visited
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..20 {
+            let mut contents = gen::wires(seed, 40, 60);
+            contents.push_str(gen::wires(seed + 100, 40, 60).lines().next().unwrap());
+            let input = parse_input(&contents);
+            assert_eq!(input.wires.len(), 3);
+            let wires: Vec<Vec<Segment>> = input
+                .wires
+                .iter()
+                .enumerate()
+                .map(|(i, m)| trace(i, m))
+                .collect();
+            let crossings = find_crossings(&wires);
+            let expected = brute_force(&wires);
+            assert_eq!(crossings.len(), expected.len());
+            for c in crossings {
+                assert_eq!(c.steps, expected[&c.point]);
+            }
+        }
+    }
+}
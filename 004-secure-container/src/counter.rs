@@ -0,0 +1,245 @@
+use crate::rules::{part1_rule, part2_rule, Digits, Rule};
+use crate::PasswordCounts;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+// numbers in 1..=n matching the rule, built digit by digit from the most
+// significant one, with every number below n grouped by what the rule can
+// still tell about it
+pub fn count_up_to(n: u64, rule: &Rule) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let digits: Vec<u8> = n.to_string().bytes().map(|b| b - b'0').collect();
+    let projection = rule.projection();
+    let mut below: HashMap<Digits, u64> = HashMap::new();
+    let mut tight = Digits::default();
+    for (i, &limit) in digits.iter().enumerate() {
+        let mut next: HashMap<Digits, u64> = HashMap::new();
+        let mut add = |state: Digits, count: u64| {
+            *next.entry(projection.project(&state)).or_default() += count;
+        };
+        for (state, count) in below {
+            for digit in 0..=9 {
+                add(state.push(digit), count);
+            }
+        }
+        // numbers with fewer digits than n start after the first position
+        if i > 0 {
+            for digit in 1..=9 {
+                add(Digits::default().push(digit), 1);
+            }
+        }
+        let lowest = if i == 0 { 1 } else { 0 };
+        for digit in lowest..limit {
+            add(tight.push(digit), 1);
+        }
+        tight = tight.push(limit);
+        below = next;
+    }
+
+    below
+        .into_iter()
+        .chain(Some((tight, 1)))
+        .filter(|(state, _)| rule.eval(&state.finish(), None))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+// custom rules can't be split over digits, so with one of those every
+// candidate in the range gets checked
+pub fn count(range: &RangeInclusive<u64>, rule: &Rule) -> u64 {
+    if rule.has_custom() {
+        return passwords(range, rule).count() as u64;
+    }
+    let mut res = count_up_to(*range.end(), rule);
+    if *range.start() == 0 {
+        if rule.matches(0) {
+            res += 1;
+        }
+    } else {
+        res -= count_up_to(range.start() - 1, rule);
+    }
+    res
+}
+
+pub fn count_parts(range: &RangeInclusive<u64>) -> PasswordCounts {
+    PasswordCounts {
+        valid: count(range, &part1_rule()),
+        valid_with_extra_rule: count(range, &part2_rule()),
+    }
+}
+
+// smallest number at least n whose digits never decrease, if it fits a u64
+pub fn next_non_decreasing(n: u64) -> Option<u64> {
+    let mut digits: Vec<u8> = n.to_string().bytes().map(|b| b - b'0').collect();
+    if let Some(i) = (1..digits.len()).find(|&i| digits[i] < digits[i - 1]) {
+        let fill = digits[i - 1];
+        for digit in digits[i..].iter_mut() {
+            *digit = fill;
+        }
+    }
+    let next = digits
+        .iter()
+        .fold(0u128, |acc, &digit| acc * 10 + digit as u128);
+    if next > u64::MAX as u128 {
+        return None;
+    }
+    Some(next as u64)
+}
+
+// matching passwords in ascending order, when the rule only allows digits
+// that never decrease it jumps straight to the next such number instead of
+// checking every number
+pub struct Passwords<'a> {
+    next: Option<u64>,
+    end: u64,
+    rule: &'a Rule,
+    non_decreasing: bool,
+}
+impl<'a> Iterator for Passwords<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            let candidate = if self.non_decreasing {
+                self.next.and_then(next_non_decreasing)
+            } else {
+                self.next
+            };
+            match candidate {
+                Some(n) if n <= self.end => {
+                    self.next = n.checked_add(1);
+                    if self.rule.matches(n) {
+                        return Some(n);
+                    }
+                }
+                _ => {
+                    self.next = None;
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+pub fn passwords<'a>(range: &RangeInclusive<u64>, rule: &'a Rule) -> Passwords<'a> {
+    Passwords {
+        next: Some(*range.start()),
+        end: *range.end(),
+        rule,
+        non_decreasing: rule.requires_non_decreasing(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{count, count_parts, count_up_to, next_non_decreasing, passwords};
+    use crate::rules::{part1_rule, part2_rule, Rule};
+    use crate::{brute_force_count, get_potential_password_count};
+
+    #[test]
+    fn matches_brute_force_on_puzzle_range() {
+        let range = 137683..=596253;
+        assert_eq!(count_parts(&range), get_potential_password_count(&range));
+    }
+
+    #[test]
+    fn matches_brute_force_on_small_ranges() {
+        for range in [
+            0..=0,
+            0..=11,
+            1..=2000,
+            99990..=100100,
+            111110..=111122,
+            555555..=555555,
+            987..=123456,
+        ] {
+            assert_eq!(count_parts(&range), get_potential_password_count(&range));
+        }
+    }
+
+    #[test]
+    fn any_rule_matches_brute_force() {
+        let rules = [
+            Rule::RunAtLeast(3),
+            Rule::RunExactly(1).and(Rule::Length(4)),
+            !Rule::NonDecreasing,
+            Rule::Forbidden(vec![3, 7]).or(Rule::RunExactly(3)),
+            part2_rule().and(!Rule::Forbidden(vec![0, 5])),
+            Rule::Length(3)
+                .or(Rule::Length(5))
+                .and(!Rule::RunAtLeast(2)),
+            Rule::custom(|p| p % 7 == 0).and(Rule::RunAtLeast(2)),
+            part1_rule().and(Rule::custom(|p| p % 2 == 1)),
+        ];
+        for rule in rules.iter() {
+            for range in [0..=25000, 98765..=123456] {
+                assert_eq!(count(&range, rule), brute_force_count(&range, rule));
+                assert_eq!(
+                    passwords(&range, rule).collect::<Vec<u64>>(),
+                    range
+                        .clone()
+                        .filter(|&p| rule.matches(p))
+                        .collect::<Vec<u64>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn counts_by_length() {
+        // non-decreasing numbers of d digits, minus the strictly increasing ones
+        let choose = |n: u64, k: u64| (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1));
+        let mut start = 1;
+        for digits in 1..=19 {
+            let end = start * 10 - 1;
+            let expected = choose(digits + 8, 8) - if digits <= 9 { choose(9, digits) } else { 0 };
+            assert_eq!(count(&(start..=end), &part1_rule()), expected);
+            start *= 10;
+        }
+    }
+
+    #[test]
+    fn whole_u64_range() {
+        let all = count_parts(&(0..=u64::MAX));
+        let below = count_parts(&(0..=9_999_999_999_999_999_999));
+        let above = count_parts(&(10_000_000_000_000_000_000..=u64::MAX));
+        assert_eq!(all.valid, count_up_to(u64::MAX, &part1_rule()));
+        assert_eq!(all.valid, below.valid + above.valid);
+        assert_eq!(
+            all.valid_with_extra_rule,
+            below.valid_with_extra_rule + above.valid_with_extra_rule
+        );
+        assert_eq!(
+            above.valid,
+            count(&(11_111_111_111_111_111_111..=u64::MAX), &part1_rule())
+        );
+    }
+
+    #[test]
+    fn next_non_decreasing_numbers() {
+        assert_eq!(next_non_decreasing(0), Some(0));
+        assert_eq!(next_non_decreasing(137683), Some(137777));
+        assert_eq!(next_non_decreasing(599999), Some(599999));
+        assert_eq!(next_non_decreasing(590000), Some(599999));
+        assert_eq!(next_non_decreasing(u64::MAX), None);
+    }
+
+    #[test]
+    fn lazy_enumeration() {
+        let range = 137683..=596253;
+        let (part1, part2) = (part1_rule(), part2_rule());
+        assert_eq!(passwords(&range, &part2).count(), 1258);
+        assert_eq!(passwords(&range, &part1).count(), 1864);
+        assert_eq!(passwords(&range, &part1).next(), Some(137777));
+        assert_eq!(
+            passwords(&(u64::MAX - 1000..=u64::MAX), &part1).next(),
+            None
+        );
+        assert_eq!(
+            passwords(&(99_999_999_999_999_999..=u64::MAX), &part2).next(),
+            Some(111_111_111_111_111_122)
+        );
+    }
+}
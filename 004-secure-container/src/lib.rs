@@ -0,0 +1,127 @@
+pub mod counter;
+pub mod rules;
+
+use rules::Rule;
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PasswordCounts {
+    pub valid: u64,
+    pub valid_with_extra_rule: u64,
+}
+
+// the puzzle input is the range itself, e.g. 137683-596253
+pub fn parse_range(contents: &str) -> Result<RangeInclusive<u64>, String> {
+    let line = contents.trim();
+    let mut bounds = line.splitn(2, '-');
+    let start = bounds.next().unwrap_or("").trim();
+    let end = bounds
+        .next()
+        .ok_or_else(|| format!("Expected a range like 137683-596253, got '{}'", line))?
+        .trim();
+    let start = start
+        .parse::<u64>()
+        .map_err(|e| format!("Invalid range start '{}': {}", start, e))?;
+    let end = end
+        .parse::<u64>()
+        .map_err(|e| format!("Invalid range end '{}': {}", end, e))?;
+    if start > end {
+        return Err(format!("Range start {} is after its end {}", start, end));
+    }
+    Ok(start..=end)
+}
+
+pub fn get_potential_password_count(range: &RangeInclusive<u64>) -> PasswordCounts {
+    let mut count = 0;
+    let mut count2 = 0;
+    for pass in range.clone() {
+        let (is_valid, is_valid_with_extra_rule) = is_valid_password(pass, range);
+        if is_valid {
+            count += 1;
+        }
+        if is_valid_with_extra_rule {
+            count2 += 1;
+        }
+    }
+    PasswordCounts {
+        valid: count,
+        valid_with_extra_rule: count2,
+    }
+}
+
+pub fn is_valid_password(password: u64, range: &RangeInclusive<u64>) -> (bool, bool) {
+    if !range.contains(&password) {
+        return (false, false);
+    }
+
+    (
+        rules::part1_rule().matches(password),
+        rules::part2_rule().matches(password),
+    )
+}
+
+// checks every number in the range against the rule
+pub fn brute_force_count(range: &RangeInclusive<u64>, rule: &Rule) -> u64 {
+    range.clone().filter(|&p| rule.matches(p)).count() as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_potential_password_count, is_valid_password, parse_range};
+    use std::ops::RangeInclusive;
+
+    const RANGE: RangeInclusive<u64> = 137683..=596253;
+
+    #[test]
+    fn first_sample_input() {
+        assert_eq!(is_valid_password(222222, &RANGE), (true, false));
+    }
+
+    #[test]
+    fn second_sample_input() {
+        assert_eq!(is_valid_password(223450, &RANGE), (false, false));
+    }
+
+    #[test]
+    fn third_sample_input() {
+        assert_eq!(is_valid_password(234789, &RANGE), (false, false));
+    }
+
+    #[test]
+    fn fourth_sample_input() {
+        assert_eq!(is_valid_password(223344, &RANGE), (true, true));
+    }
+
+    #[test]
+    fn fifth_sample_input() {
+        assert_eq!(is_valid_password(234555, &RANGE), (true, false));
+    }
+
+    #[test]
+    fn sixth_sample_input() {
+        assert_eq!(is_valid_password(222233, &RANGE), (true, true));
+    }
+
+    #[test]
+    fn outside_of_range() {
+        assert_eq!(is_valid_password(112233, &RANGE), (false, false));
+        assert_eq!(is_valid_password(667788, &RANGE), (false, false));
+        assert_eq!(is_valid_password(112233, &(0..=999999)), (true, true));
+    }
+
+    #[test]
+    fn parse_ranges() {
+        assert_eq!(parse_range("137683-596253\n"), Ok(RANGE));
+        assert_eq!(parse_range(" 0 - 18446744073709551615 "), Ok(0..=u64::MAX));
+        assert!(parse_range("137683").is_err());
+        assert!(parse_range("5-x").is_err());
+        assert!(parse_range("9-5").is_err());
+    }
+
+    #[test]
+    fn puzzle_range() {
+        let counts =
+            get_potential_password_count(&parse_range(include_str!("../input.txt")).unwrap());
+        assert_eq!((counts.valid, counts.valid_with_extra_rule), (1864, 1258));
+    }
+}
@@ -1,89 +1,46 @@
-use std::collections::HashMap;
+use aoc_bench::{input_source, read_input, Bench};
+use secure_container::{counter, get_potential_password_count, parse_range, rules};
+use std::env;
 
 fn main() {
-    let (passwords1, passwords2) = get_potential_password_count();
-    println!("Secure Container part1 Solution: {}", passwords1);
-    println!("Secure Container part2 Solution: {}", passwords2);
-}
-
-fn get_potential_password_count() -> (i32, i32) {
-    let mut count = 0;
-    let mut count2 = 0;
-    for pass in 137683..=596253 {
-        let (is_valid, is_valid_with_extra_rule) = is_valid_password(pass);
-        if is_valid {
-            count = count + 1;
-        }
-        if is_valid_with_extra_rule {
-            count2 = count2 + 1;
-        }
-    }
-    (count, count2)
-}
-
-fn is_valid_password(password: i32) -> (bool, bool) {
-    if password < 137683 && password > 596253 {
-        return (false, false);
-    }
-
-    let mut prev_char: char = ' ';
-    let str_pass = password.to_string();
-    let mut str_pass_chars = str_pass.chars();
-    let mut has_repeated_digits = false;
-    let mut repeadet_digits: HashMap<char, i32> = HashMap::new();
-    while let Some(digit) = str_pass_chars.next() {
-        if prev_char != ' ' {
-            if prev_char > digit {
-                return (false, false);
-            } else if prev_char == digit {
-                has_repeated_digits = true;
-                if let Some(digit_count) = repeadet_digits.get(&digit) {
-                    repeadet_digits.insert(digit, digit_count + 1);
-                } else {
-                    repeadet_digits.insert(digit, 1);
-                }
-            }
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "bench" {
+        // both parts are counted in the same pass over the range
+        let mut bench = Bench::from_args(&args[2..]);
+        let contents = read_input(&bench.input);
+        let range = parse_range(&contents).unwrap();
+        bench.time("parse", || parse_range(&contents));
+        bench.time("brute force", || get_potential_password_count(&range));
+        bench.time("parts", || counter::count_parts(&range));
+        bench.finish();
+        return;
+    }
+
+    // list <part> [limit] prints the passwords in the range one per line
+    if args.len() > 2 && args[1] == "list" {
+        let rule = match args[2].as_str() {
+            "part1" => rules::part1_rule(),
+            "part2" => rules::part2_rule(),
+            _ => panic!("Expected part1 or part2"),
+        };
+        let limit = args
+            .get(3)
+            .map(|l| l.parse().expect("Invalid limit"))
+            .unwrap_or(usize::MAX);
+        let source = input_source(None);
+        let range = parse_range(&read_input(&source)).unwrap();
+        for password in counter::passwords(&range, &rule).take(limit) {
+            println!("{}", password);
         }
-        prev_char = digit;
+        return;
     }
 
-    return (
-        has_repeated_digits,
-        repeadet_digits.values().find(|&&v| v == 1).is_some(),
+    let source = input_source(env::args().nth(1));
+    let range = parse_range(&read_input(&source)).unwrap();
+    let password_counts = counter::count_parts(&range);
+    println!("Secure Container part1 Solution: {}", password_counts.valid);
+    println!(
+        "Secure Container part2 Solution: {}",
+        password_counts.valid_with_extra_rule
     );
 }
-
-#[cfg(test)]
-mod test {
-    use super::is_valid_password;
-
-    #[test]
-    fn first_sample_input() {
-        assert_eq!(is_valid_password(222222), (true, false));
-    }
-
-    #[test]
-    fn second_sample_input() {
-        assert_eq!(is_valid_password(223450), (false, false));
-    }
-
-    #[test]
-    fn third_sample_input() {
-        assert_eq!(is_valid_password(234789), (false, false));
-    }
-
-    #[test]
-    fn fourth_sample_input() {
-        assert_eq!(is_valid_password(223344), (true, true));
-    }
-
-    #[test]
-    fn fifth_sample_input() {
-        assert_eq!(is_valid_password(234555), (true, false));
-    }
-
-    #[test]
-    fn sixth_sample_input() {
-        assert_eq!(is_valid_password(222233), (true, true));
-    }
-}
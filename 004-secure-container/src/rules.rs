@@ -0,0 +1,291 @@
+use std::ops::Not;
+use std::rc::Rc;
+
+// everything the rules can ask about a number, gathered one digit at a time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Digits {
+    pub length: u8,
+    pub last: u8,
+    // length of the run of `last` the number currently ends in
+    pub run: u8,
+    // longest finished run
+    pub longest: u8,
+    // bit n is set once a run of exactly n digits is finished
+    pub runs: u32,
+    // bit d is set once the digit d is used
+    pub used: u16,
+    pub descended: bool,
+}
+impl Digits {
+    pub fn of(password: u64) -> Digits {
+        let mut digits = Digits::default();
+        for b in password.to_string().bytes() {
+            digits = digits.push(b - b'0');
+        }
+        digits.finish()
+    }
+
+    pub fn push(&self, digit: u8) -> Digits {
+        let mut next = *self;
+        next.length += 1;
+        next.used |= 1 << digit;
+        if self.length > 0 && digit == self.last {
+            next.run += 1;
+            return next;
+        }
+        if self.length > 0 {
+            next = next.finish();
+            next.descended |= digit < self.last;
+        }
+        next.last = digit;
+        next.run = 1;
+        next
+    }
+
+    // closes the current run, the number may still go on with another one
+    pub fn finish(&self) -> Digits {
+        let mut next = *self;
+        next.longest = self.longest.max(self.run);
+        next.runs |= 1 << self.run;
+        next
+    }
+}
+
+#[derive(Clone)]
+pub enum Rule {
+    NonDecreasing,
+    RunAtLeast(u8),
+    RunExactly(u8),
+    Length(u8),
+    Forbidden(Vec<u8>),
+    Custom(Rc<dyn Fn(u64) -> bool>),
+    And(Vec<Rule>),
+    Or(Vec<Rule>),
+    Not(Box<Rule>),
+}
+impl Rule {
+    pub fn custom<F: Fn(u64) -> bool + 'static>(f: F) -> Rule {
+        Rule::Custom(Rc::new(f))
+    }
+
+    pub fn and(self, other: Rule) -> Rule {
+        match self {
+            Rule::And(mut rules) => {
+                rules.push(other);
+                Rule::And(rules)
+            }
+            rule => Rule::And(vec![rule, other]),
+        }
+    }
+
+    pub fn or(self, other: Rule) -> Rule {
+        match self {
+            Rule::Or(mut rules) => {
+                rules.push(other);
+                Rule::Or(rules)
+            }
+            rule => Rule::Or(vec![rule, other]),
+        }
+    }
+
+    pub fn matches(&self, password: u64) -> bool {
+        self.eval(&Digits::of(password), Some(password))
+    }
+
+    // custom rules need the number itself, the others only its digits
+    pub fn eval(&self, digits: &Digits, password: Option<u64>) -> bool {
+        match self {
+            Rule::NonDecreasing => !digits.descended,
+            Rule::RunAtLeast(n) => digits.longest >= *n,
+            Rule::RunExactly(n) => *n < 32 && digits.runs & (1 << n) != 0,
+            Rule::Length(n) => digits.length == *n,
+            Rule::Forbidden(forbidden) => forbidden.iter().all(|&d| digits.used & (1 << d) == 0),
+            Rule::Custom(f) => f(password.expect("Custom rules need the password")),
+            Rule::And(rules) => rules.iter().all(|r| r.eval(digits, password)),
+            Rule::Or(rules) => rules.iter().any(|r| r.eval(digits, password)),
+            Rule::Not(rule) => !rule.eval(digits, password),
+        }
+    }
+
+    pub fn has_custom(&self) -> bool {
+        match self {
+            Rule::Custom(_) => true,
+            Rule::And(rules) | Rule::Or(rules) => rules.iter().any(|r| r.has_custom()),
+            Rule::Not(rule) => rule.has_custom(),
+            _ => false,
+        }
+    }
+
+    // whether every match is bound to have digits that never decrease
+    pub fn requires_non_decreasing(&self) -> bool {
+        match self {
+            Rule::NonDecreasing => true,
+            Rule::And(rules) => rules.iter().any(|r| r.requires_non_decreasing()),
+            Rule::Or(rules) => {
+                !rules.is_empty() && rules.iter().all(|r| r.requires_non_decreasing())
+            }
+            _ => false,
+        }
+    }
+
+    // the part of the digits this rule looks at, so the counter only tells
+    // apart numbers the rule can tell apart
+    pub fn projection(&self) -> Projection {
+        let mut projection = Projection::default();
+        self.track(&mut projection);
+        projection
+    }
+
+    fn track(&self, tracked: &mut Projection) {
+        match self {
+            Rule::NonDecreasing => tracked.descended = true,
+            Rule::RunAtLeast(n) => tracked.longest = tracked.longest.max(*n),
+            Rule::RunExactly(n) => {
+                let n = (*n).min(31);
+                tracked.runs |= 1 << n;
+                tracked.run = tracked.run.max(n + 1);
+            }
+            Rule::Length(_) => tracked.length = true,
+            Rule::Forbidden(forbidden) => {
+                for &d in forbidden {
+                    tracked.used |= 1 << d;
+                }
+            }
+            Rule::Custom(_) => {}
+            Rule::And(rules) | Rule::Or(rules) => rules.iter().for_each(|r| r.track(tracked)),
+            Rule::Not(rule) => rule.track(tracked),
+        }
+    }
+}
+impl Not for Rule {
+    type Output = Rule;
+
+    fn not(self) -> Rule {
+        match self {
+            Rule::Not(rule) => *rule,
+            rule => Rule::Not(Box::new(rule)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Projection {
+    length: bool,
+    descended: bool,
+    longest: u8,
+    runs: u32,
+    run: u8,
+    used: u16,
+}
+impl Projection {
+    pub fn project(&self, digits: &Digits) -> Digits {
+        // runs longer than any the rules mention all look the same
+        let run_cap = self.run.max(self.longest);
+        let needs_last = self.descended || run_cap > 0;
+        Digits {
+            // a number that has started still has to look started
+            length: if self.length {
+                digits.length
+            } else {
+                digits.length.min(1)
+            },
+            last: if needs_last { digits.last } else { 0 },
+            run: digits.run.min(run_cap),
+            longest: digits.longest.min(self.longest),
+            runs: digits.runs & self.runs,
+            used: digits.used & self.used,
+            descended: self.descended && digits.descended,
+        }
+    }
+}
+
+// the puzzle's rules, the range takes care of the six digits
+pub fn part1_rule() -> Rule {
+    Rule::NonDecreasing.and(Rule::RunAtLeast(2))
+}
+
+pub fn part2_rule() -> Rule {
+    Rule::NonDecreasing.and(Rule::RunExactly(2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{part1_rule, part2_rule, Digits, Rule};
+
+    #[test]
+    fn digits_of_a_number() {
+        let digits = Digits::of(1122333);
+        assert_eq!(digits.length, 7);
+        assert_eq!(digits.longest, 3);
+        assert_eq!(digits.runs, 1 << 2 | 1 << 3);
+        assert_eq!(digits.used, 1 << 1 | 1 << 2 | 1 << 3);
+        assert!(!digits.descended);
+        assert!(Digits::of(1210).descended);
+        assert_eq!(Digits::of(0).length, 1);
+    }
+
+    #[test]
+    fn puzzle_rules() {
+        for (password, expected) in [
+            (111111, (true, false)),
+            (223450, (false, false)),
+            (123789, (false, false)),
+            (112233, (true, true)),
+            (123444, (true, false)),
+            (111122, (true, true)),
+        ] {
+            assert_eq!(
+                (
+                    part1_rule().matches(password),
+                    part2_rule().matches(password)
+                ),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn combinators() {
+        let rule = Rule::Length(6)
+            .and(Rule::RunAtLeast(3).or(Rule::RunExactly(2)))
+            .and(!Rule::Forbidden(vec![0, 9]));
+        assert!(rule.matches(100023));
+        assert!(rule.matches(191123));
+        assert!(!rule.matches(123456));
+        assert!(!rule.matches(112345));
+        assert!(!rule.matches(1112345));
+        assert!(!rule.has_custom());
+        assert!(!rule.requires_non_decreasing());
+        assert!(part1_rule().requires_non_decreasing());
+        assert!(!(!Rule::NonDecreasing).requires_non_decreasing());
+    }
+
+    #[test]
+    fn custom_rules() {
+        let even = Rule::custom(|p| p % 2 == 0);
+        let rule = part2_rule().and(even.clone());
+        assert!(rule.matches(112234));
+        assert!(!rule.matches(112233));
+        assert!(rule.has_custom());
+        assert!((!even).matches(7));
+    }
+
+    #[test]
+    fn projection_keeps_what_the_rule_needs() {
+        let prefix = |digits: &[u8]| {
+            digits
+                .iter()
+                .fold(Digits::default(), |acc, &digit| acc.push(digit))
+        };
+        let projection = part2_rule().projection();
+        let a = projection.project(&prefix(&[1, 1, 1, 2, 3]));
+        let b = projection.project(&prefix(&[2, 2, 3, 3, 4]));
+        assert_eq!(a.runs, 0);
+        assert_eq!(b.runs, 1 << 2);
+        assert_eq!(a.used, 0);
+        assert_eq!(a.length, 1);
+        assert_eq!(projection.project(&Digits::of(1111111)).run, 3);
+        let projection = Rule::RunExactly(255).projection();
+        assert_eq!(projection.project(&Digits::of(1111111)).run, 7);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::lca::OrbitTree;
+use serde::Serialize;
+use std::collections::HashSet;
+
+#[derive(Debug, Serialize)]
+pub struct Body {
+    pub name: String,
+    // direct and indirect orbits of the body itself
+    pub depth: usize,
+    // orbits of the body and of everything orbiting it
+    pub orbits: usize,
+    pub highlighted: bool,
+    pub children: Vec<Body>,
+}
+
+fn subtree_orbits(tree: &OrbitTree) -> Vec<usize> {
+    let mut orbits = tree.depth.clone();
+    // children are numbered after their parents
+    for id in (0..orbits.len()).rev() {
+        if let Some(p) = tree.parent[id] {
+            orbits[p] += orbits[id];
+        }
+    }
+    orbits
+}
+
+// the bodies on the path between two bodies, both included
+pub fn highlight<'a>(tree: &'a OrbitTree, path: Option<(&str, &str)>) -> Vec<&'a str> {
+    match path {
+        Some((a, b)) => tree.path(a, b).unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+pub fn to_dot(tree: &OrbitTree, highlighted: &[&str]) -> String {
+    let orbits = subtree_orbits(tree);
+    let on_path: HashSet<&str> = highlighted.iter().copied().collect();
+    let mut lines = vec![
+        String::from("digraph orbits {"),
+        String::from("    rankdir=LR;"),
+        String::from("    node [shape=box, fontsize=10];"),
+    ];
+    for (id, name) in tree.names.iter().enumerate() {
+        let style = if on_path.contains(name.as_str()) {
+            ", color=red, style=filled, fillcolor=mistyrose"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "    \"{}\" [label=\"{}\\ndepth {}\\norbits {}\"{}];",
+            name, name, tree.depth[id], orbits[id], style
+        ));
+    }
+    // consecutive bodies of the path are the highlighted edges
+    let edges: HashSet<(&str, &str)> = highlighted
+        .windows(2)
+        .flat_map(|w| vec![(w[0], w[1]), (w[1], w[0])])
+        .collect();
+    for (id, name) in tree.names.iter().enumerate() {
+        if let Some(p) = tree.parent[id] {
+            let parent = tree.names[p].as_str();
+            let style = if edges.contains(&(parent, name.as_str())) {
+                " [color=red, penwidth=3]"
+            } else {
+                ""
+            };
+            lines.push(format!("    \"{}\" -> \"{}\"{};", parent, name, style));
+        }
+    }
+    lines.push(String::from("}"));
+    lines.join("\n")
+}
+
+// one nested body per root
+pub fn to_bodies(tree: &OrbitTree, highlighted: &[&str]) -> Vec<Body> {
+    let orbits = subtree_orbits(tree);
+    let on_path: HashSet<&str> = highlighted.iter().copied().collect();
+    let mut bodies: Vec<Option<Body>> = tree
+        .names
+        .iter()
+        .enumerate()
+        .map(|(id, name)| {
+            Some(Body {
+                name: name.clone(),
+                depth: tree.depth[id],
+                orbits: orbits[id],
+                highlighted: on_path.contains(name.as_str()),
+                children: Vec::new(),
+            })
+        })
+        .collect();
+    // built bottom up, every child is done before its parent takes it
+    for id in (0..bodies.len()).rev() {
+        let children: Vec<Body> = tree.children[id]
+            .iter()
+            .map(|&child| bodies[child].take().unwrap())
+            .collect();
+        bodies[id].as_mut().unwrap().children = children;
+    }
+    bodies.into_iter().flatten().collect()
+}
+
+pub fn to_json(tree: &OrbitTree, highlighted: &[&str]) -> String {
+    serde_json::to_string_pretty(&to_bodies(tree, highlighted)).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{highlight, to_bodies, to_dot, to_json};
+    use crate::init_tree;
+    use crate::lca::OrbitTree;
+
+    fn sample() -> OrbitTree {
+        OrbitTree::new(&init_tree(vec![
+            "COM)B", "B)C", "C)D", "D)E", "E)F", "B)G", "G)H", "D)I", "E)J", "J)K", "K)L", "K)YOU",
+            "I)SAN",
+        ]))
+    }
+
+    #[test]
+    fn dot_export() {
+        let tree = sample();
+        let path = highlight(&tree, Some(("YOU", "SAN")));
+        let dot = to_dot(&tree, &path);
+        assert!(dot.starts_with("digraph orbits {"));
+        assert!(dot.ends_with('}'));
+        assert_eq!(dot.matches(" -> ").count(), 13);
+        assert!(dot.contains("\"COM\" [label=\"COM\\ndepth 0\\norbits 54\"];"));
+        assert!(dot.contains("\"L\" [label=\"L\\ndepth 7\\norbits 7\"];"));
+        assert!(dot.contains("\"K\" [label=\"K\\ndepth 6\\norbits 20\", color=red"));
+        assert!(dot.contains("\"D\" -> \"I\" [color=red, penwidth=3];"));
+        assert!(dot.contains("\"K\" -> \"L\";"));
+        assert_eq!(dot.matches("color=red").count(), 7 + 6);
+    }
+
+    #[test]
+    fn json_export() {
+        let tree = sample();
+        let bodies = to_bodies(&tree, &highlight(&tree, Some(("YOU", "SAN"))));
+        assert_eq!(bodies.len(), 1);
+        let com = &bodies[0];
+        assert_eq!((com.name.as_str(), com.depth, com.orbits), ("COM", 0, 54));
+        assert!(!com.highlighted);
+        let b = &com.children[0];
+        let names: Vec<&str> = b.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["C", "G"]);
+        let d = &b.children[0].children[0];
+        assert!(d.highlighted);
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&tree, &[])).unwrap();
+        assert_eq!(json[0]["name"], "COM");
+        assert_eq!(json[0]["children"][0]["children"][1]["name"], "G");
+        assert_eq!(json[0]["children"][0]["children"][1]["orbits"], 2 + 3);
+        assert_eq!(json[0]["highlighted"], false);
+    }
+
+    #[test]
+    fn missing_path_highlights_nothing() {
+        let tree = sample();
+        assert!(highlight(&tree, Some(("YOU", "NOBODY"))).is_empty());
+        assert!(highlight(&tree, None).is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+const NAME_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+// every body orbits one of the `spread` bodies created right before it,
+// so a spread of 1 gives a single chain and a large spread a bushy tree
+pub fn orbit_map(seed: u64, bodies: usize, spread: usize) -> String {
+    assert!(bodies >= 3, "The map needs at least COM, YOU and SAN");
+    assert!(spread >= 1, "Spread has to be at least 1");
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut used: HashSet<String> = ["COM", "YOU", "SAN"]
+        .iter()
+        .map(|n| n.to_string())
+        .collect();
+    // names get longer with the map so there are always plenty left to pick
+    let mut length = 3;
+    while NAME_CHARS.len().pow(length as u32) < 2 * bodies {
+        length += 1;
+    }
+    let mut names = vec![String::from("COM")];
+    let mut relations = Vec::new();
+    for i in 1..bodies - 2 {
+        let mut name = random_name(&mut rng, length);
+        while used.contains(&name) {
+            name = random_name(&mut rng, length);
+        }
+        used.insert(name.clone());
+        let parent = rng.gen_range(i.saturating_sub(spread), i);
+        relations.push(format!("{}){}", names[parent], name));
+        names.push(name);
+    }
+
+    // YOU and SAN orbit two different bodies
+    let you_parent = rng.gen_range(0, names.len());
+    let mut san_parent = rng.gen_range(0, names.len());
+    while names.len() > 1 && san_parent == you_parent {
+        san_parent = rng.gen_range(0, names.len());
+    }
+    relations.push(format!("{})YOU", names[you_parent]));
+    relations.push(format!("{})SAN", names[san_parent]));
+
+    relations.shuffle(&mut rng);
+    let mut contents = relations.join("\n");
+    contents.push('\n');
+    contents
+}
+
+fn random_name(rng: &mut StdRng, length: usize) -> String {
+    (0..length)
+        .map(|_| NAME_CHARS[rng.gen_range(0, NAME_CHARS.len())] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::orbit_map;
+    use crate::{parse_input, part1, part2};
+
+    #[test]
+    fn same_seed_same_map() {
+        assert_eq!(orbit_map(3, 200, 10), orbit_map(3, 200, 10));
+        assert_ne!(orbit_map(3, 200, 10), orbit_map(4, 200, 10));
+    }
+
+    #[test]
+    fn single_chain() {
+        let map = parse_input(&orbit_map(1, 10, 1));
+        assert_eq!(map.nodes.len(), 10);
+        assert!(part1(&map) >= (0..8).sum::<i32>() + 2);
+    }
+
+    #[test]
+    fn more_bodies_than_three_letter_names() {
+        let map = parse_input(&orbit_map(2, 50000, 1000));
+        assert_eq!(map.nodes.len(), 50000);
+        assert!(map.nodes.keys().any(|n| n.len() == 4));
+    }
+
+    #[test]
+    fn generated_maps_are_trees() {
+        for seed in 0..20 {
+            let map = parse_input(&orbit_map(seed, 100, 5));
+            assert_eq!(map.nodes.len(), 100);
+            assert_eq!(map.nodes.values().filter(|n| n.parent.is_none()).count(), 1);
+            assert!(part2(&map) < i32::MAX);
+        }
+    }
+}
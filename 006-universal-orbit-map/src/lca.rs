@@ -0,0 +1,287 @@
+use crate::Node;
+use std::collections::HashMap;
+
+// the orbit map with every body numbered, so the lowest common ancestor of
+// any two bodies can be found by binary lifting in O(log n)
+pub struct OrbitTree {
+    pub names: Vec<String>,
+    pub ids: HashMap<String, usize>,
+    pub parent: Vec<Option<usize>>,
+    pub children: Vec<Vec<usize>>,
+    pub depth: Vec<usize>,
+    pub subtree_size: Vec<usize>,
+    // up[k][v] is the 2^k-th ancestor of v, or its root when it has fewer
+    up: Vec<Vec<usize>>,
+}
+impl OrbitTree {
+    // bodies that can't be reached from a body orbiting nothing are left out
+    pub fn new(nodes: &HashMap<String, Node>) -> OrbitTree {
+        let mut roots: Vec<&String> = nodes
+            .values()
+            .filter(|n| n.parent.is_none())
+            .map(|n| &n.name)
+            .collect();
+        roots.sort();
+
+        let mut names: Vec<String> = Vec::new();
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut parent: Vec<Option<usize>> = Vec::new();
+        let mut depth: Vec<usize> = Vec::new();
+        // breadth first, so every parent is numbered before its children
+        let mut queue: Vec<(String, Option<usize>)> =
+            roots.into_iter().map(|r| (r.clone(), None)).collect();
+        let mut i = 0;
+        while i < queue.len() {
+            let (name, parent_id) = queue[i].clone();
+            i += 1;
+            if ids.contains_key(&name) {
+                continue;
+            }
+            let id = names.len();
+            ids.insert(name.clone(), id);
+            names.push(name.clone());
+            parent.push(parent_id);
+            depth.push(parent_id.map_or(0, |p| depth[p] + 1));
+            if let Some(node) = nodes.get(&name) {
+                let mut children = node.children.clone();
+                children.sort();
+                for child in children {
+                    queue.push((child, Some(id)));
+                }
+            }
+        }
+
+        let len = names.len();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); len];
+        let mut subtree_size = vec![1; len];
+        for id in (0..len).rev() {
+            if let Some(p) = parent[id] {
+                children[p].push(id);
+                subtree_size[p] += subtree_size[id];
+            }
+        }
+        for c in children.iter_mut() {
+            c.reverse();
+        }
+
+        let mut up: Vec<Vec<usize>> = vec![(0..len).map(|v| parent[v].unwrap_or(v)).collect()];
+        let levels = (usize::BITS - len.leading_zeros()).max(1) as usize;
+        for k in 1..levels {
+            let prev = &up[k - 1];
+            let next: Vec<usize> = (0..len).map(|v| prev[prev[v]]).collect();
+            up.push(next);
+        }
+
+        OrbitTree {
+            names,
+            ids,
+            parent,
+            children,
+            depth,
+            subtree_size,
+            up,
+        }
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    // direct and indirect orbits of every body
+    pub fn total_orbits(&self) -> usize {
+        self.depth.iter().sum()
+    }
+
+    pub fn depth_of(&self, name: &str) -> Option<usize> {
+        self.id(name).map(|id| self.depth[id])
+    }
+
+    pub fn subtree_size_of(&self, name: &str) -> Option<usize> {
+        self.id(name).map(|id| self.subtree_size[id])
+    }
+
+    // the body itself followed by everything it orbits, up to the root
+    pub fn ancestor_path(&self, name: &str) -> Option<Vec<&str>> {
+        let mut curr = self.id(name);
+        let mut path = Vec::new();
+        while let Some(id) = curr {
+            path.push(self.names[id].as_str());
+            curr = self.parent[id];
+        }
+        if path.is_empty() {
+            return None;
+        }
+        Some(path)
+    }
+
+    fn ancestor(&self, id: usize, steps: usize) -> usize {
+        let mut curr = id;
+        for (k, up) in self.up.iter().enumerate() {
+            if steps >> k & 1 == 1 {
+                curr = up[curr];
+            }
+        }
+        curr
+    }
+
+    fn lca_id(&self, a: usize, b: usize) -> Option<usize> {
+        let (mut a, mut b) = if self.depth[a] < self.depth[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        a = self.ancestor(a, self.depth[a] - self.depth[b]);
+        if a == b {
+            return Some(a);
+        }
+        for up in self.up.iter().rev() {
+            if up[a] != up[b] {
+                a = up[a];
+                b = up[b];
+            }
+        }
+        // bodies of separate trees end up at their roots and never meet
+        self.parent[a]
+    }
+
+    pub fn lca(&self, a: &str, b: &str) -> Option<&str> {
+        let id = self.lca_id(self.id(a)?, self.id(b)?)?;
+        Some(self.names[id].as_str())
+    }
+
+    // orbits between the two bodies
+    pub fn distance(&self, a: &str, b: &str) -> Option<usize> {
+        let (a, b) = (self.id(a)?, self.id(b)?);
+        let lca = self.lca_id(a, b)?;
+        Some(self.depth[a] + self.depth[b] - 2 * self.depth[lca])
+    }
+
+    // orbital transfers to move a to the body b is orbiting
+    pub fn transfers(&self, a: &str, b: &str) -> Option<usize> {
+        let a = self.parent[self.id(a)?]?;
+        let b = self.parent[self.id(b)?]?;
+        self.distance(&self.names[a], &self.names[b])
+    }
+
+    // every body from a to b, both included
+    pub fn path(&self, a: &str, b: &str) -> Option<Vec<&str>> {
+        let lca = self.lca(a, b)?;
+        let mut path = self.ancestor_path(a)?;
+        path.truncate(path.iter().position(|&n| n == lca).unwrap() + 1);
+        let mut back = self.ancestor_path(b)?;
+        back.truncate(back.iter().position(|&n| n == lca).unwrap());
+        path.extend(back.into_iter().rev());
+        Some(path)
+    }
+}
+
+// pairs of bodies, one pair per line separated by spaces, commas or ')'
+pub fn parse_queries(contents: &str) -> Result<Vec<(String, String)>, String> {
+    let mut queries = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let bodies: Vec<&str> = line
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ')')
+            .filter(|b| !b.is_empty())
+            .collect();
+        if bodies.len() != 2 {
+            return Err(format!(
+                "line {}: expected two bodies, got '{}'",
+                i + 1,
+                line
+            ));
+        }
+        queries.push((bodies[0].to_string(), bodies[1].to_string()));
+    }
+    Ok(queries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_queries, OrbitTree};
+    use crate::{
+        calculate_orbital_transfers_to_santa, calculate_orbits_number, gen, init_tree, parse_input,
+    };
+    use std::collections::HashSet;
+
+    fn sample() -> OrbitTree {
+        OrbitTree::new(&init_tree(vec![
+            "COM)B", "B)C", "C)D", "D)E", "E)F", "B)G", "G)H", "D)I", "E)J", "J)K", "K)L", "K)YOU",
+            "I)SAN",
+        ]))
+    }
+
+    #[test]
+    fn sample_queries() {
+        let tree = sample();
+        assert_eq!(tree.transfers("YOU", "SAN"), Some(4));
+        assert_eq!(tree.transfers("SAN", "YOU"), Some(4));
+        assert_eq!(tree.lca("YOU", "SAN"), Some("D"));
+        assert_eq!(tree.lca("L", "E"), Some("E"));
+        assert_eq!(tree.distance("H", "F"), Some(6));
+        assert_eq!(tree.distance("K", "K"), Some(0));
+        assert_eq!(tree.depth_of("L"), Some(7));
+        assert_eq!(tree.subtree_size_of("E"), Some(6));
+        assert_eq!(tree.subtree_size_of("COM"), Some(14));
+        assert_eq!(
+            tree.ancestor_path("J"),
+            Some(vec!["J", "E", "D", "C", "B", "COM"])
+        );
+        assert_eq!(
+            tree.path("YOU", "SAN"),
+            Some(vec!["YOU", "K", "J", "E", "D", "I", "SAN"])
+        );
+        assert_eq!(tree.transfers("COM", "SAN"), None);
+        assert_eq!(tree.depth_of("X"), None);
+        assert_eq!(tree.total_orbits(), 42 + 7 + 5);
+    }
+
+    #[test]
+    fn separate_trees_dont_meet() {
+        let tree = OrbitTree::new(&init_tree(vec!["A)B", "B)C", "X)Y", "Y)Z"]));
+        assert_eq!(tree.lca("C", "Z"), None);
+        assert_eq!(tree.distance("B", "Y"), None);
+        assert_eq!(tree.distance("A", "C"), Some(2));
+    }
+
+    #[test]
+    fn matches_walking_up_the_tree() {
+        let map = parse_input(&gen::orbit_map(7, 600, 5));
+        let tree = OrbitTree::new(&map.nodes);
+        let naive = |a: &str, b: &str| {
+            let up_a = tree.ancestor_path(a).unwrap();
+            let up_b = tree.ancestor_path(b).unwrap();
+            let lca = up_a.iter().find(|n| up_b.contains(n)).unwrap();
+            up_a.iter().position(|n| n == lca).unwrap()
+                + up_b.iter().position(|n| n == lca).unwrap()
+        };
+        for a in tree.names.iter().step_by(7) {
+            for b in tree.names.iter().step_by(11) {
+                assert_eq!(tree.distance(a, b), Some(naive(a, b)));
+            }
+        }
+        let root = map.nodes["COM"].clone();
+        assert_eq!(
+            tree.total_orbits() as i32,
+            calculate_orbits_number(root, &map.nodes, 0)
+        );
+        let you = map.nodes["YOU"].parent.as_ref().unwrap();
+        assert_eq!(
+            tree.transfers("YOU", "SAN").unwrap() as i32,
+            calculate_orbital_transfers_to_santa(you, &map.nodes, 0, HashSet::new())
+        );
+    }
+
+    #[test]
+    fn query_file() {
+        let queries = parse_queries("YOU SAN\n\nA)B\n C , D \n").unwrap();
+        assert_eq!(queries.len(), 3);
+        assert_eq!(queries[2], (String::from("C"), String::from("D")));
+        assert_eq!(
+            parse_queries("YOU SAN\nYOU\n"),
+            Err(String::from("line 2: expected two bodies, got 'YOU'"))
+        );
+    }
+}
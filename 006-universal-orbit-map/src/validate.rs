@@ -0,0 +1,347 @@
+use crate::lca::OrbitTree;
+use crate::{init_tree, relation_lines};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    // not of the form A)B
+    Malformed {
+        line: usize,
+        text: String,
+    },
+    // the later relation is ignored
+    TwoParents {
+        line: usize,
+        body: String,
+        parent: String,
+        first_line: usize,
+        first_parent: String,
+    },
+    // relations, in order, of bodies orbiting each other in a loop
+    Cycle {
+        lines: Vec<usize>,
+        bodies: Vec<String>,
+    },
+    // every root with the line it first shows up on
+    MultipleRoots {
+        roots: Vec<(String, usize)>,
+    },
+}
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Malformed { line, text } => {
+                write!(f, "line {}: expected A)B, got '{}'", line, text)
+            }
+            Problem::TwoParents {
+                line,
+                body,
+                parent,
+                first_line,
+                first_parent,
+            } => write!(
+                f,
+                "line {}: {} orbits {}, but already orbits {} on line {}",
+                line, body, parent, first_parent, first_line
+            ),
+            Problem::Cycle { lines, bodies } => {
+                let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+                write!(
+                    f,
+                    "lines {}: {} orbit each other in a cycle",
+                    lines.join(", "),
+                    bodies.join(" -> ")
+                )
+            }
+            Problem::MultipleRoots { roots } => {
+                let roots: Vec<String> = roots
+                    .iter()
+                    .map(|(root, line)| format!("{} (line {})", root, line))
+                    .collect();
+                write!(
+                    f,
+                    "{} bodies orbit nothing: {}",
+                    roots.len(),
+                    roots.join(", ")
+                )
+            }
+        }
+    }
+}
+
+// a relation that made it into the map, parent first
+#[derive(Clone, Debug, PartialEq)]
+pub struct Relation {
+    pub line: usize,
+    pub parent: String,
+    pub body: String,
+}
+
+// every well formed relation giving a body its first parent, along with the
+// problems found on the way
+pub fn check_relations(contents: &str) -> (Vec<Relation>, Vec<Problem>) {
+    let mut relations: Vec<Relation> = Vec::new();
+    let mut problems: Vec<Problem> = Vec::new();
+    let mut parents: HashMap<String, usize> = HashMap::new();
+    for (line, text) in relation_lines(contents) {
+        let elements: Vec<&str> = text.split(')').collect();
+        if elements.len() != 2 || elements.iter().any(|e| e.is_empty()) {
+            problems.push(Problem::Malformed {
+                line,
+                text: text.clone(),
+            });
+            continue;
+        }
+        let (parent, body) = (elements[0].to_string(), elements[1].to_string());
+        if let Some(&first) = parents.get(&body) {
+            problems.push(Problem::TwoParents {
+                line,
+                body,
+                parent,
+                first_line: relations[first].line,
+                first_parent: relations[first].parent.clone(),
+            });
+            continue;
+        }
+        parents.insert(body.clone(), relations.len());
+        relations.push(Relation { line, parent, body });
+    }
+
+    problems.extend(find_cycles(&relations));
+
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+    for r in relations.iter() {
+        first_seen.entry(&r.parent).or_insert(r.line);
+        first_seen.entry(&r.body).or_insert(r.line);
+    }
+    let mut roots: Vec<(String, usize)> = first_seen
+        .into_iter()
+        .filter(|(body, _)| !parents.contains_key(*body))
+        .map(|(body, line)| (body.to_string(), line))
+        .collect();
+    if roots.len() > 1 {
+        roots.sort_by_key(|(_, line)| *line);
+        problems.push(Problem::MultipleRoots { roots });
+    }
+
+    (relations, problems)
+}
+
+pub fn validate(contents: &str) -> Vec<Problem> {
+    check_relations(contents).1
+}
+
+fn find_cycles(relations: &[Relation]) -> Vec<Problem> {
+    let parent_of: HashMap<&str, &Relation> =
+        relations.iter().map(|r| (r.body.as_str(), r)).collect();
+    // 0 not visited yet, 1 on the current walk, 2 done
+    let mut state: HashMap<&str, u8> = HashMap::new();
+    let mut cycles = Vec::new();
+    for start in relations.iter() {
+        let mut walk: Vec<&Relation> = Vec::new();
+        let mut curr = start.body.as_str();
+        while state.get(curr).copied().unwrap_or(0) == 0 {
+            state.insert(curr, 1);
+            match parent_of.get(curr) {
+                Some(r) => {
+                    walk.push(r);
+                    curr = &r.parent;
+                }
+                None => break,
+            }
+        }
+        if state.get(curr) == Some(&1) && parent_of.contains_key(curr) {
+            // curr is where the walk closed on itself
+            let from = walk.iter().position(|r| r.body == curr).unwrap();
+            let mut on_cycle: Vec<&Relation> = walk[from..].to_vec();
+            let first = (0..on_cycle.len())
+                .min_by_key(|&i| on_cycle[i].line)
+                .unwrap();
+            on_cycle.rotate_left(first);
+            cycles.push(Problem::Cycle {
+                lines: on_cycle.iter().map(|r| r.line).collect(),
+                bodies: on_cycle.iter().map(|r| r.body.clone()).collect(),
+            });
+        }
+        for r in walk.iter() {
+            state.insert(&r.body, 2);
+        }
+        state.insert(curr, 2);
+    }
+    cycles
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+    pub bodies: Vec<String>,
+    // none when the bodies orbit each other in a cycle
+    pub root: Option<String>,
+    pub orbits: Option<usize>,
+    // when both YOU and SAN are part of it
+    pub transfers: Option<usize>,
+}
+
+// bodies connected by the well formed relations, each solved on its own
+pub fn components(contents: &str) -> Vec<Component> {
+    let (relations, _) = check_relations(contents);
+    let mut group: HashMap<&str, usize> = HashMap::new();
+    let mut members: Vec<Vec<&str>> = Vec::new();
+    for r in relations.iter() {
+        match (group.get(r.parent.as_str()), group.get(r.body.as_str())) {
+            (None, None) => {
+                group.insert(&r.parent, members.len());
+                group.insert(&r.body, members.len());
+                members.push(vec![&r.parent, &r.body]);
+            }
+            (Some(&g), None) => {
+                group.insert(&r.body, g);
+                members[g].push(&r.body);
+            }
+            (None, Some(&g)) => {
+                group.insert(&r.parent, g);
+                members[g].push(&r.parent);
+            }
+            (Some(&a), Some(&b)) if a != b => {
+                let moved = std::mem::take(&mut members[b]);
+                for body in moved.iter() {
+                    group.insert(body, a);
+                }
+                members[a].extend(moved);
+            }
+            _ => {}
+        }
+    }
+
+    let mut by_group: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for r in relations.iter() {
+        by_group
+            .entry(group[r.body.as_str()])
+            .or_default()
+            .push(format!("{}){}", r.parent, r.body));
+    }
+    let mut res: Vec<Component> = by_group
+        .into_iter()
+        .map(|(g, lines)| {
+            let tree = OrbitTree::new(&init_tree(lines.iter().map(|l| l.as_str()).collect()));
+            let mut bodies: Vec<String> = members[g].iter().map(|b| b.to_string()).collect();
+            bodies.sort();
+            // a body orbiting itself joins its component twice
+            bodies.dedup();
+            let solvable = tree.names.len() == bodies.len();
+            Component {
+                bodies,
+                root: tree.names.first().filter(|_| solvable).cloned(),
+                orbits: Some(tree.total_orbits()).filter(|_| solvable),
+                transfers: tree.transfers("YOU", "SAN").filter(|_| solvable),
+            }
+        })
+        .collect();
+    res.sort_by_key(|c| Reverse(c.bodies.len()));
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::{components, validate, Problem};
+
+    #[test]
+    fn valid_map() {
+        assert!(validate(include_str!("../input.txt")).is_empty());
+        assert!(validate("COM)B\nB)C\n\n").is_empty());
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(
+            validate("COM)B\nB-C\nC)\nA)B)C\n"),
+            vec![
+                Problem::Malformed {
+                    line: 2,
+                    text: String::from("B-C")
+                },
+                Problem::Malformed {
+                    line: 3,
+                    text: String::from("C)")
+                },
+                Problem::Malformed {
+                    line: 4,
+                    text: String::from("A)B)C")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn two_parents() {
+        let problems = validate("COM)B\nB)C\nCOM)C\n");
+        assert_eq!(
+            problems,
+            vec![Problem::TwoParents {
+                line: 3,
+                body: String::from("C"),
+                parent: String::from("COM"),
+                first_line: 2,
+                first_parent: String::from("B"),
+            }]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "line 3: C orbits COM, but already orbits B on line 2"
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let problems = validate("COM)B\nX)Y\nY)Z\nZ)X\nQ)Q\n");
+        assert_eq!(
+            problems,
+            vec![
+                Problem::Cycle {
+                    lines: vec![2, 4, 3],
+                    bodies: vec![String::from("Y"), String::from("X"), String::from("Z")],
+                },
+                Problem::Cycle {
+                    lines: vec![5],
+                    bodies: vec![String::from("Q")],
+                },
+            ]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "lines 2, 4, 3: Y -> X -> Z orbit each other in a cycle"
+        );
+    }
+
+    #[test]
+    fn multiple_roots() {
+        let problems = validate("COM)B\nB)C\nA)D\n");
+        assert_eq!(
+            problems,
+            vec![Problem::MultipleRoots {
+                roots: vec![(String::from("COM"), 1), (String::from("A"), 3)]
+            }]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "2 bodies orbit nothing: COM (line 1), A (line 3)"
+        );
+    }
+
+    #[test]
+    fn results_per_component() {
+        let contents = "COM)B\nB)C\nC)YOU\nB)SAN\nA)D\nD)E\nX)Y\nY)X\nbroken\nC)D\n";
+        let res = components(contents);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0].root, Some(String::from("COM")));
+        assert_eq!(res[0].orbits, Some(1 + 2 + 3 + 2));
+        assert_eq!(res[0].transfers, Some(1));
+        assert_eq!(res[1].bodies, vec!["A", "D", "E"]);
+        assert_eq!(res[1].orbits, Some(3));
+        assert_eq!(res[1].transfers, None);
+        assert_eq!(res[2].root, None);
+        assert_eq!(res[2].orbits, None);
+        assert_eq!(components("Q)Q\n")[0].bodies, vec!["Q"]);
+    }
+}
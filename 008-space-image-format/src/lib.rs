@@ -0,0 +1,35 @@
+pub mod sif;
+pub mod stats;
+
+use sif::{Pixel, SifImage};
+use stats::LayerStats;
+
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+pub fn parse_input(contents: &str) -> SifImage {
+    parse_image(contents, WIDTH, HEIGHT)
+}
+
+pub fn parse_image(contents: &str, width: usize, height: usize) -> SifImage {
+    SifImage::decode(contents, width, height).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn part1(image: &SifImage) -> usize {
+    calculate_layer_value(image)
+}
+
+pub fn part2(image: &SifImage) -> Vec<Pixel> {
+    image.render()
+}
+
+// ones times twos on the layer with the fewest zeros
+pub fn calculate_layer_value(image: &SifImage) -> usize {
+    let stats = LayerStats::from_image(image);
+    let layer = stats.argmin('0').unwrap();
+    stats.count(layer, '1') * stats.count(layer, '2')
+}
+
+pub fn print_image(image: &[Pixel], width: usize) {
+    println!("{}", sif::to_text(image, width))
+}
@@ -1,112 +1,118 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use aoc_bench::{input_source, read_input, take_option, Bench};
+use space_image_format::sif::SifImage;
+use space_image_format::stats::{visible_layers, Checksum, LayerStats};
+use space_image_format::{parse_image, parse_input, part1, part2, print_image, HEIGHT, WIDTH};
+use std::env;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Failed to read input file");
-    let mut layers = read_layers(contents);
-
-    println!(
-        "Space Image Format part1 Solution: {:?}",
-        calculate_layer_value(&mut layers)
-    );
-
-    let final_image = calculate_visible_pixel_values(&mut layers);
-    println!("Space Image Format part2 Solution: \n",);
-    print_image(final_image);
-}
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "bench" {
+        let mut bench = Bench::from_args(&args[2..]);
+        let contents = read_input(&bench.input);
+        let input = parse_input(&contents);
+        bench.time("parse", || parse_input(&contents));
+        bench.time("part1", || part1(&input));
+        bench.time("part2", || part2(&input));
+        bench.finish();
+        return;
+    }
 
-#[derive(Clone, Debug)]
-struct Layer {
-    values: Vec<Vec<char>>,
-    value_count: HashMap<char, i32>,
-}
-impl Layer {
-    pub fn new() -> Layer {
-        Layer {
-            values: vec![vec![' '; 6]; 25],
-            value_count: HashMap::new(),
-        }
+    // render <width> <height> [input] prints an image of any size
+    if args.len() > 3 && args[1] == "render" {
+        let (width, height) = (size_arg(&args[2]), size_arg(&args[3]));
+        let source = input_source(args.get(4).cloned());
+        let image = parse_image(&read_input(&source), width, height);
+        print_image(&image.render(), width);
+        return;
     }
-}
 
-fn calculate_visible_pixel_values(layers: &mut Vec<Layer>) -> Vec<Vec<char>> {
-    let mut values = vec![vec![' '; 6]; 25];
-    for i in 0..25 {
-        for j in 0..6 {
-            let mut layer_index = 0;
-            let mut pixel_color = layers[layer_index].values[i][j];
-            while pixel_color == '2' {
-                layer_index = layer_index + 1;
-                pixel_color = layers[layer_index].values[i][j];
+    // stats [input] [--size <w> <h>] [--checksum <expr>] [--pick <min|max> <digit>]
+    // prints every layer's histogram and the checksum of the picked layer
+    if args.len() > 1 && args[1] == "stats" {
+        let mut rest: Vec<String> = args[2..].to_vec();
+        let size = take_option(&mut rest, "--size", 2)
+            .unwrap_or_else(|| vec![WIDTH.to_string(), HEIGHT.to_string()]);
+        let expression = take_option(&mut rest, "--checksum", 1)
+            .map_or(String::from("#1 * #2"), |o| o[0].clone());
+        let pick = take_option(&mut rest, "--pick", 2)
+            .unwrap_or_else(|| vec![String::from("min"), String::from("0")]);
+        let source = input_source(rest.first().cloned());
+        let stats =
+            LayerStats::from_data(&read_input(&source), size_arg(&size[0]), size_arg(&size[1]))
+                .unwrap_or_else(|e| panic!("{}", e));
+        let alphabet = stats.alphabet();
+        let mut header = format!("{:>6}", "layer");
+        for digit in alphabet.iter() {
+            header.push_str(&format!("{:>6}", digit));
+        }
+        println!("{}", header);
+        for layer in 0..stats.histograms.len() {
+            let mut line = format!("{:>6}", layer);
+            for &digit in alphabet.iter() {
+                line.push_str(&format!("{:>6}", stats.count(layer, digit)));
             }
-            values[i][j] = pixel_color;
+            println!("{}", line);
         }
+        let digit = pick[1].chars().next().expect("Expected a digit to pick by");
+        let layer = match pick[0].as_str() {
+            "min" => stats.argmin(digit),
+            "max" => stats.argmax(digit),
+            _ => panic!("Expected min or max"),
+        }
+        .unwrap();
+        let checksum = Checksum::parse(&expression).unwrap_or_else(|e| panic!("{}", e));
+        match stats.checksum(layer, &checksum) {
+            Ok(value) => println!(
+                "layer {} ({} #{}): {} = {}",
+                layer, pick[0], digit, expression, value
+            ),
+            Err(e) => println!("layer {}: {}", layer, e),
+        }
+        return;
     }
-    values
-}
 
-fn calculate_layer_value(layers: &mut Vec<Layer>) -> i32 {
-    let mut layer_with_min0 = Layer::new();
-    let mut curr_layer = 0;
-    let mut minimum = i32::max_value();
-
-    for layer in layers {
-        let zero_count = layer.value_count.get(&'0').unwrap();
-        if minimum > *zero_count {
-            minimum = *zero_count;
-            layer_with_min0 = layer.clone();
+    // visible <width> <height> [input] prints which layer every pixel comes from
+    if args.len() > 3 && args[1] == "visible" {
+        let (width, height) = (size_arg(&args[2]), size_arg(&args[3]));
+        let source = input_source(args.get(4).cloned());
+        let image = parse_image(&read_input(&source), width, height);
+        let layers = visible_layers(&image);
+        for row in layers.chunks(width) {
+            let row: Vec<String> = row
+                .iter()
+                .map(|layer| layer.map_or(String::from("  -"), |l| format!("{:>3}", l)))
+                .collect();
+            println!("{}", row.join(""));
         }
-        curr_layer = curr_layer + 1;
+        return;
     }
-    layer_with_min0.value_count.get(&'1').unwrap() * layer_with_min0.value_count.get(&'2').unwrap()
-}
 
-fn read_layers(input: String) -> Vec<Layer> {
-    let mut layers = Vec::new();
-    let mut current_layer = 0;
-    let mut i = 0;
-    let mut j = 0;
-    let mut input_chars = input.chars();
-    while let Some(ch) = input_chars.next() {
-        let curr = current_layer;
-        if i == 0 && j == 0 {
-            layers.push(Layer::new());
-        }
-        if i == 24 {
-            i = 0;
-            if j == 5 {
-                j = 0;
-                current_layer = current_layer + 1;
-            } else {
-                j = j + 1;
-            }
-        } else {
-            i = i + 1;
-        }
-        layers[curr].values[i][j] = ch;
-        if let Some(&value_count) = layers[curr].value_count.get(&ch) {
-            &layers[curr].value_count.insert(ch, value_count + 1);
-        } else {
-            layers[curr].value_count.insert(ch, 1);
+    // composite <width> <height> <image>... stacks the layers of every image
+    // into one, the first image on top, and prints it as SIF
+    if args.len() > 4 && args[1] == "composite" {
+        let (width, height) = (size_arg(&args[2]), size_arg(&args[3]));
+        let images: Vec<SifImage> = args[4..]
+            .iter()
+            .map(|source| parse_image(&read_input(source), width, height))
+            .collect();
+        match SifImage::composite(&images) {
+            Ok(image) => println!("{}", image.encode()),
+            Err(e) => panic!("{}", e),
         }
+        return;
     }
-    layers
+
+    let source = input_source(env::args().nth(1));
+    let contents = read_input(&source);
+    let image = parse_input(&contents);
+
+    println!("Space Image Format part1 Solution: {:?}", part1(&image));
+
+    let final_image = part2(&image);
+    println!("Space Image Format part2 Solution: \n",);
+    print_image(&final_image, WIDTH);
 }
 
-fn print_image(image: Vec<Vec<char>>) {
-    let mut sb = String::new();
-    for i in 0..6 {
-        for j in 0..25 {
-            match image[j][i] {
-                '0' => sb.push(' '),
-                _ => sb.push('|'),
-            }
-        }
-        sb.push('\n');
-    }
-    println!("{}", sb)
+fn size_arg(arg: &str) -> usize {
+    arg.parse().expect("Invalid image size")
 }
@@ -0,0 +1,278 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pixel {
+    Black,
+    White,
+    Transparent,
+}
+impl Pixel {
+    pub fn from_digit(digit: char) -> Option<Pixel> {
+        match digit {
+            '0' => Some(Pixel::Black),
+            '1' => Some(Pixel::White),
+            '2' => Some(Pixel::Transparent),
+            _ => None,
+        }
+    }
+
+    pub fn to_digit(self) -> char {
+        match self {
+            Pixel::Black => '0',
+            Pixel::White => '1',
+            Pixel::Transparent => '2',
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SifError {
+    ZeroSize,
+    Empty,
+    // the data doesn't split into whole layers
+    Length {
+        length: usize,
+        layer_size: usize,
+    },
+    Digit {
+        position: usize,
+        found: char,
+    },
+    // images with other dimensions can't share layers
+    Dimensions {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+}
+impl fmt::Display for SifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SifError::ZeroSize => write!(f, "Width and height have to be at least 1"),
+            SifError::Empty => write!(f, "The image has no layers"),
+            SifError::Length { length, layer_size } => write!(
+                f,
+                "{} digits don't make whole layers of {} pixels",
+                length, layer_size
+            ),
+            SifError::Digit { position, found } => {
+                write!(f, "Unexpected '{}' at position {}", found, position)
+            }
+            SifError::Dimensions { expected, found } => write!(
+                f,
+                "Expected a {}x{} image, got {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+        }
+    }
+}
+
+// the raw digits of every layer, once the length is known to be right
+pub fn split_layers(data: &str, width: usize, height: usize) -> Result<Vec<&str>, SifError> {
+    if width == 0 || height == 0 {
+        return Err(SifError::ZeroSize);
+    }
+    // the trailing newline of a file isn't a pixel
+    let data = data.trim_end();
+    if data.is_empty() {
+        return Err(SifError::Empty);
+    }
+    if let Some((position, found)) = data.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(SifError::Digit { position, found });
+    }
+    let layer_size = width * height;
+    if !data.len().is_multiple_of(layer_size) {
+        return Err(SifError::Length {
+            length: data.len(),
+            layer_size,
+        });
+    }
+    Ok((0..data.len() / layer_size)
+        .map(|i| &data[i * layer_size..(i + 1) * layer_size])
+        .collect())
+}
+
+// layers are stored top first, each one row by row
+#[derive(Clone, Debug, PartialEq)]
+pub struct SifImage {
+    pub width: usize,
+    pub height: usize,
+    pub layers: Vec<Vec<Pixel>>,
+}
+impl SifImage {
+    pub fn decode(data: &str, width: usize, height: usize) -> Result<SifImage, SifError> {
+        let mut layers = Vec::new();
+        for (i, layer) in split_layers(data, width, height)?.into_iter().enumerate() {
+            let mut pixels = Vec::with_capacity(layer.len());
+            for (j, digit) in layer.chars().enumerate() {
+                match Pixel::from_digit(digit) {
+                    Some(pixel) => pixels.push(pixel),
+                    None => {
+                        return Err(SifError::Digit {
+                            position: i * width * height + j,
+                            found: digit,
+                        })
+                    }
+                }
+            }
+            layers.push(pixels);
+        }
+        Ok(SifImage {
+            width,
+            height,
+            layers,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        self.layers
+            .iter()
+            .flatten()
+            .map(|pixel| pixel.to_digit())
+            .collect()
+    }
+
+    pub fn pixel(&self, layer: usize, x: usize, y: usize) -> Pixel {
+        self.layers[layer][y * self.width + x]
+    }
+
+    // the first pixel that isn't transparent, looking from the top layer down
+    pub fn render(&self) -> Vec<Pixel> {
+        (0..self.width * self.height)
+            .map(|i| {
+                self.layers
+                    .iter()
+                    .map(|layer| layer[i])
+                    .find(|&pixel| pixel != Pixel::Transparent)
+                    .unwrap_or(Pixel::Transparent)
+            })
+            .collect()
+    }
+
+    // a single layer image of what is visible
+    pub fn flatten(&self) -> SifImage {
+        SifImage {
+            width: self.width,
+            height: self.height,
+            layers: vec![self.render()],
+        }
+    }
+
+    // stacks the layers of every image, the first image ending up on top
+    pub fn composite(images: &[SifImage]) -> Result<SifImage, SifError> {
+        let first = images.first().ok_or(SifError::Empty)?;
+        let mut layers = Vec::new();
+        for image in images {
+            if (image.width, image.height) != (first.width, first.height) {
+                return Err(SifError::Dimensions {
+                    expected: (first.width, first.height),
+                    found: (image.width, image.height),
+                });
+            }
+            layers.extend(image.layers.iter().cloned());
+        }
+        Ok(SifImage {
+            width: first.width,
+            height: first.height,
+            layers,
+        })
+    }
+}
+
+pub fn to_text(pixels: &[Pixel], width: usize) -> String {
+    pixels
+        .chunks(width)
+        .map(|row| {
+            row.iter()
+                .map(|pixel| match pixel {
+                    Pixel::Black => ' ',
+                    Pixel::White => '|',
+                    Pixel::Transparent => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{split_layers, to_text, Pixel, SifError, SifImage};
+
+    #[test]
+    fn decode_sample() {
+        let image = SifImage::decode("123456789012\n", 3, 2);
+        assert_eq!(
+            image,
+            Err(SifError::Digit {
+                position: 2,
+                found: '3'
+            })
+        );
+        assert_eq!(
+            split_layers("123456789012\n", 3, 2),
+            Ok(vec!["123456", "789012"])
+        );
+
+        let image = SifImage::decode("0222112222120000\n", 2, 2).unwrap();
+        assert_eq!(image.layers.len(), 4);
+        assert_eq!(image.pixel(1, 0, 0), Pixel::White);
+        assert_eq!(image.pixel(1, 0, 1), Pixel::Transparent);
+        assert_eq!(
+            image.render(),
+            vec![Pixel::Black, Pixel::White, Pixel::White, Pixel::Black]
+        );
+        assert_eq!(to_text(&image.render(), 2), " |\n| ");
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert_eq!(SifImage::decode("0122", 0, 2), Err(SifError::ZeroSize));
+        assert_eq!(SifImage::decode("\n", 2, 2), Err(SifError::Empty));
+        assert_eq!(
+            SifImage::decode("01220", 2, 2),
+            Err(SifError::Length {
+                length: 5,
+                layer_size: 4
+            })
+        );
+        assert_eq!(
+            SifImage::decode("01x2", 2, 2),
+            Err(SifError::Digit {
+                position: 2,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            SifImage::decode("01220", 2, 2).unwrap_err().to_string(),
+            "5 digits don't make whole layers of 4 pixels"
+        );
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let data = include_str!("../input.txt").trim_end();
+        let image = SifImage::decode(data, 25, 6).unwrap();
+        assert_eq!(image.layers.len(), 100);
+        assert_eq!(image.encode(), data);
+        assert_eq!(image.flatten().render(), image.render());
+        assert_eq!(image.flatten().encode().len(), 25 * 6);
+    }
+
+    #[test]
+    fn composite_images() {
+        let top = SifImage::decode("2202", 2, 2).unwrap();
+        let bottom = SifImage::decode("11112222", 2, 2).unwrap();
+        let image = SifImage::composite(&[top, bottom.clone()]).unwrap();
+        assert_eq!(image.encode(), "220211112222");
+        assert_eq!(to_text(&image.render(), 2), "||\n |");
+        let other = SifImage::decode("111", 3, 1).unwrap();
+        assert_eq!(
+            SifImage::composite(&[bottom, other]),
+            Err(SifError::Dimensions {
+                expected: (2, 2),
+                found: (3, 1)
+            })
+        );
+        assert_eq!(SifImage::composite(&[]), Err(SifError::Empty));
+    }
+}
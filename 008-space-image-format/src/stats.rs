@@ -0,0 +1,262 @@
+use crate::sif::{split_layers, Pixel, SifError, SifImage};
+use std::collections::BTreeMap;
+
+// how often every digit shows up on every layer, for any alphabet of digits
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerStats {
+    pub histograms: Vec<BTreeMap<char, usize>>,
+}
+impl LayerStats {
+    pub fn from_image(image: &SifImage) -> LayerStats {
+        LayerStats::from_layers(image.layers.iter().map(|layer| {
+            layer
+                .iter()
+                .map(|pixel| pixel.to_digit())
+                .collect::<String>()
+        }))
+    }
+
+    pub fn from_data(data: &str, width: usize, height: usize) -> Result<LayerStats, SifError> {
+        Ok(LayerStats::from_layers(
+            split_layers(data, width, height)?
+                .into_iter()
+                .map(|layer| layer.to_string()),
+        ))
+    }
+
+    fn from_layers<I: Iterator<Item = String>>(layers: I) -> LayerStats {
+        let histograms = layers
+            .map(|layer| {
+                let mut histogram: BTreeMap<char, usize> = BTreeMap::new();
+                for digit in layer.chars() {
+                    *histogram.entry(digit).or_default() += 1;
+                }
+                histogram
+            })
+            .collect();
+        LayerStats { histograms }
+    }
+
+    // every digit used on any layer
+    pub fn alphabet(&self) -> Vec<char> {
+        let mut alphabet: Vec<char> = self
+            .histograms
+            .iter()
+            .flat_map(|h| h.keys())
+            .copied()
+            .collect();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+        alphabet
+    }
+
+    pub fn count(&self, layer: usize, digit: char) -> usize {
+        self.histograms[layer].get(&digit).copied().unwrap_or(0)
+    }
+
+    // the first layer with the fewest of the digit
+    pub fn argmin(&self, digit: char) -> Option<usize> {
+        (0..self.histograms.len()).min_by_key(|&layer| self.count(layer, digit))
+    }
+
+    // the first layer with the most of the digit
+    pub fn argmax(&self, digit: char) -> Option<usize> {
+        (0..self.histograms.len())
+            .rev()
+            .max_by_key(|&layer| self.count(layer, digit))
+    }
+
+    pub fn checksum(&self, layer: usize, checksum: &Checksum) -> Result<i64, String> {
+        checksum.eval(&|digit| self.count(layer, digit) as i64)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Checksum {
+    // #d is how many times the digit d shows up on the layer
+    Count(char),
+    Number(i64),
+    Negate(Box<Checksum>),
+    Add(Box<Checksum>, Box<Checksum>),
+    Subtract(Box<Checksum>, Box<Checksum>),
+    Multiply(Box<Checksum>, Box<Checksum>),
+    Divide(Box<Checksum>, Box<Checksum>),
+}
+impl Checksum {
+    // e.g. the puzzle's "#1 * #2", with + - * / and parentheses
+    pub fn parse(expression: &str) -> Result<Checksum, String> {
+        let tokens: Vec<char> = expression.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut position = 0;
+        let checksum = parse_sum(&tokens, &mut position)?;
+        if position < tokens.len() {
+            return Err(format!("Unexpected '{}' in checksum", tokens[position]));
+        }
+        Ok(checksum)
+    }
+
+    pub fn eval(&self, count: &dyn Fn(char) -> i64) -> Result<i64, String> {
+        let overflow = || String::from("Checksum overflows");
+        match self {
+            Checksum::Count(digit) => Ok(count(*digit)),
+            Checksum::Number(n) => Ok(*n),
+            Checksum::Negate(a) => a.eval(count)?.checked_neg().ok_or_else(overflow),
+            Checksum::Add(a, b) => a
+                .eval(count)?
+                .checked_add(b.eval(count)?)
+                .ok_or_else(overflow),
+            Checksum::Subtract(a, b) => a
+                .eval(count)?
+                .checked_sub(b.eval(count)?)
+                .ok_or_else(overflow),
+            Checksum::Multiply(a, b) => a
+                .eval(count)?
+                .checked_mul(b.eval(count)?)
+                .ok_or_else(overflow),
+            Checksum::Divide(a, b) => {
+                let divisor = b.eval(count)?;
+                if divisor == 0 {
+                    return Err(String::from("Checksum divides by zero"));
+                }
+                a.eval(count)?.checked_div(divisor).ok_or_else(overflow)
+            }
+        }
+    }
+}
+
+fn parse_sum(tokens: &[char], position: &mut usize) -> Result<Checksum, String> {
+    let mut left = parse_product(tokens, position)?;
+    while let Some(&op) = tokens.get(*position).filter(|&&c| c == '+' || c == '-') {
+        *position += 1;
+        let right = Box::new(parse_product(tokens, position)?);
+        left = if op == '+' {
+            Checksum::Add(Box::new(left), right)
+        } else {
+            Checksum::Subtract(Box::new(left), right)
+        };
+    }
+    Ok(left)
+}
+
+fn parse_product(tokens: &[char], position: &mut usize) -> Result<Checksum, String> {
+    let mut left = parse_term(tokens, position)?;
+    while let Some(&op) = tokens.get(*position).filter(|&&c| c == '*' || c == '/') {
+        *position += 1;
+        let right = Box::new(parse_term(tokens, position)?);
+        left = if op == '*' {
+            Checksum::Multiply(Box::new(left), right)
+        } else {
+            Checksum::Divide(Box::new(left), right)
+        };
+    }
+    Ok(left)
+}
+
+fn parse_term(tokens: &[char], position: &mut usize) -> Result<Checksum, String> {
+    let token = *tokens
+        .get(*position)
+        .ok_or_else(|| String::from("Checksum ends too early"))?;
+    *position += 1;
+    match token {
+        '#' => match tokens.get(*position) {
+            Some(&digit) if digit.is_ascii_digit() => {
+                *position += 1;
+                Ok(Checksum::Count(digit))
+            }
+            _ => Err(String::from("Expected a digit after '#'")),
+        },
+        '-' => Ok(Checksum::Negate(Box::new(parse_term(tokens, position)?))),
+        '(' => {
+            let inner = parse_sum(tokens, position)?;
+            if tokens.get(*position) != Some(&')') {
+                return Err(String::from("Missing ')' in checksum"));
+            }
+            *position += 1;
+            Ok(inner)
+        }
+        d if d.is_ascii_digit() => {
+            let start = *position - 1;
+            while tokens.get(*position).is_some_and(|c| c.is_ascii_digit()) {
+                *position += 1;
+            }
+            let number: String = tokens[start..*position].iter().collect();
+            number
+                .parse()
+                .map(Checksum::Number)
+                .map_err(|e| format!("Invalid number {}: {}", number, e))
+        }
+        _ => Err(format!("Unexpected '{}' in checksum", token)),
+    }
+}
+
+// the layer each visible pixel comes from, none if every layer is transparent
+pub fn visible_layers(image: &SifImage) -> Vec<Option<usize>> {
+    (0..image.width * image.height)
+        .map(|i| {
+            image
+                .layers
+                .iter()
+                .position(|layer| layer[i] != Pixel::Transparent)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{visible_layers, Checksum, LayerStats};
+    use crate::sif::SifImage;
+    use crate::{parse_input, part1};
+
+    #[test]
+    fn histograms() {
+        let stats = LayerStats::from_data("123456789012\n", 3, 2).unwrap();
+        assert_eq!(stats.histograms.len(), 2);
+        assert_eq!(stats.count(0, '1'), 1);
+        assert_eq!(stats.count(1, '3'), 0);
+        assert_eq!(stats.alphabet().len(), 10);
+        assert_eq!(stats.argmin('7'), Some(0));
+        assert_eq!(stats.argmax('7'), Some(1));
+        // ties go to the first layer
+        assert_eq!(stats.argmax('1'), Some(0));
+        assert_eq!(stats.argmin('1'), Some(0));
+    }
+
+    #[test]
+    fn checksums() {
+        let stats = LayerStats::from_data("112233333990", 6, 1).unwrap();
+        let checksum = |expression: &str| stats.checksum(1, &Checksum::parse(expression).unwrap());
+        assert_eq!(checksum("#3"), Ok(3));
+        assert_eq!(checksum("#3 * #9 - 1"), Ok(5));
+        assert_eq!(checksum("#3 * (#9 - 1)"), Ok(3));
+        assert_eq!(checksum("-#0 + 10 / #9"), Ok(4));
+        assert_eq!(checksum("#5"), Ok(0));
+        assert_eq!(
+            checksum("#3 / #5"),
+            Err(String::from("Checksum divides by zero"))
+        );
+        assert!(Checksum::parse("#").is_err());
+        assert!(Checksum::parse("(#1").is_err());
+        assert!(Checksum::parse("#1 #2").is_err());
+        assert!(Checksum::parse("#1 ^ 2").is_err());
+    }
+
+    #[test]
+    fn puzzle_checksum() {
+        let image = parse_input(include_str!("../input.txt"));
+        let stats = LayerStats::from_image(&image);
+        let layer = stats.argmin('0').unwrap();
+        let checksum = Checksum::parse("#1 * #2").unwrap();
+        assert_eq!(stats.checksum(layer, &checksum), Ok(part1(&image) as i64));
+        assert_eq!(part1(&image), 2193);
+    }
+
+    #[test]
+    fn visible_layer_map() {
+        let image = SifImage::decode("0222112222120000", 2, 2).unwrap();
+        assert_eq!(
+            visible_layers(&image),
+            vec![Some(0), Some(1), Some(2), Some(3)]
+        );
+        let image = SifImage::decode("2122", 2, 2).unwrap();
+        assert_eq!(visible_layers(&image), vec![None, Some(0), None, None]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::cmp::Ordering;
+
+// the direction from one asteroid to another as the smallest integer vector
+// pointing that way, on the map, so y grows downwards
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Direction {
+    pub dx: i32,
+    pub dy: i32,
+}
+impl Direction {
+    pub fn new(dx: i32, dy: i32) -> Option<Direction> {
+        if dx == 0 && dy == 0 {
+            return None;
+        }
+        let g = gcd(dx.abs(), dy.abs());
+        Some(Direction {
+            dx: dx / g,
+            dy: dy / g,
+        })
+    }
+
+    pub fn between(from_x: i32, from_y: i32, to_x: i32, to_y: i32) -> Option<Direction> {
+        Direction::new(to_x - from_x, to_y - from_y)
+    }
+
+    // 0 from straight up until just before straight down, 1 for the rest
+    fn half(&self) -> u8 {
+        if self.dx > 0 || (self.dx == 0 && self.dy < 0) {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn cross(&self, other: &Direction) -> i64 {
+        self.dx as i64 * other.dy as i64 - self.dy as i64 * other.dx as i64
+    }
+}
+// clockwise, starting straight up
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.half()
+            .cmp(&other.half())
+            .then_with(|| 0.cmp(&self.cross(other)))
+    }
+}
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Direction;
+
+    #[test]
+    fn reduced_vectors() {
+        assert_eq!(Direction::new(0, 0), None);
+        assert_eq!(Direction::new(3, -6), Direction::new(1, -2));
+        assert_eq!(Direction::new(0, 7), Direction::new(0, 1));
+        assert_eq!(Direction::between(5, 5, 5, -10), Direction::new(0, -1));
+        assert_ne!(Direction::new(2, 4), Direction::new(-1, -2));
+    }
+
+    #[test]
+    fn clockwise_from_up() {
+        let order: Vec<Direction> = [
+            (0, -1),
+            (1, -3),
+            (1, -1),
+            (1, 0),
+            (2, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+            (-1, -100),
+        ]
+        .iter()
+        .map(|&(dx, dy)| Direction::new(dx, dy).unwrap())
+        .collect();
+        for (i, a) in order.iter().enumerate() {
+            for (j, b) in order.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_float_angles_where_they_are_exact_enough() {
+        let mut directions: Vec<Direction> = Vec::new();
+        for dx in -12..=12 {
+            for dy in -12..=12 {
+                if let Some(d) = Direction::new(dx, dy) {
+                    if !directions.contains(&d) {
+                        directions.push(d);
+                    }
+                }
+            }
+        }
+        let angle = |d: &Direction| {
+            let a = (d.dx as f64).atan2(-d.dy as f64);
+            if a < 0.0 {
+                a + 2.0 * std::f64::consts::PI
+            } else {
+                a
+            }
+        };
+        let mut by_float = directions.clone();
+        by_float.sort_by(|a, b| angle(a).partial_cmp(&angle(b)).unwrap());
+        directions.sort();
+        assert_eq!(directions, by_float);
+    }
+
+    #[test]
+    fn nearly_equal_directions_stay_apart() {
+        let a = Direction::new(1_000_000_000, -999_999_999).unwrap();
+        let b = Direction::new(999_999_999, -999_999_998).unwrap();
+        assert_ne!(a, b);
+        // b points a tiny bit further from straight up than a
+        assert!(a < b);
+        assert_eq!(
+            Direction::new(-2_000_000_000, 1_000_000_000),
+            Direction::new(-2, 1)
+        );
+    }
+}
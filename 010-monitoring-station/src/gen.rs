@@ -0,0 +1,60 @@
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::SeedableRng;
+
+pub fn asteroid_field(seed: u64, width: usize, height: usize, asteroids: usize) -> String {
+    assert!(asteroids >= 1, "The field needs at least one asteroid");
+    assert!(
+        asteroids <= width * height,
+        "Can't fit {} asteroids in a {}x{} field",
+        asteroids,
+        width,
+        height
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut field = vec![vec!['.'; width]; height];
+    for i in index::sample(&mut rng, width * height, asteroids).iter() {
+        field[i / width][i % width] = '#';
+    }
+
+    let mut contents = String::new();
+    for row in field {
+        contents.push_str(&row.iter().collect::<String>());
+        contents.push('\n');
+    }
+    contents
+}
+
+#[cfg(test)]
+mod test {
+    use super::asteroid_field;
+    use crate::{parse_input, part1, part2};
+
+    #[test]
+    fn same_seed_same_field() {
+        assert_eq!(asteroid_field(5, 20, 10, 50), asteroid_field(5, 20, 10, 50));
+        assert_ne!(asteroid_field(5, 20, 10, 50), asteroid_field(6, 20, 10, 50));
+    }
+
+    #[test]
+    fn field_has_requested_size() {
+        let contents = asteroid_field(1, 30, 12, 100);
+        let rows: Vec<&str> = contents.lines().collect();
+        assert_eq!(rows.len(), 12);
+        assert!(rows.iter().all(|r| r.len() == 30));
+        assert_eq!(contents.matches('#').count(), 100);
+    }
+
+    #[test]
+    fn two_asteroids_see_each_other() {
+        assert_eq!(part1(&parse_input(&asteroid_field(2, 8, 8, 2))), 1);
+    }
+
+    #[test]
+    fn generated_field_can_be_vaporized() {
+        let asteroid_map = parse_input(&asteroid_field(3, 25, 25, 300));
+        assert!(part1(&asteroid_map) >= 200);
+        part2(&asteroid_map);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::direction::Direction;
+use crate::{find_all_detections, Point};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sweep {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Laser {
+    pub x: i32,
+    pub y: i32,
+    pub sweep: Sweep,
+    // the first direction the laser fires in, asteroids straight along it
+    // are part of the first rotation
+    pub start: Direction,
+}
+impl Laser {
+    // the puzzle's laser, clockwise and starting straight up
+    pub fn new(x: i32, y: i32) -> Laser {
+        Laser {
+            x,
+            y,
+            sweep: Sweep::Clockwise,
+            start: Direction { dx: 0, dy: -1 },
+        }
+    }
+
+    // directions in the order a single rotation passes them
+    pub fn order(&self, mut directions: Vec<Direction>) -> Vec<Direction> {
+        directions.sort();
+        let first = match self.sweep {
+            Sweep::Clockwise => directions.iter().position(|d| *d >= self.start),
+            Sweep::CounterClockwise => {
+                directions.reverse();
+                directions.iter().position(|d| *d <= self.start)
+            }
+        };
+        directions.rotate_left(first.unwrap_or(0));
+        directions
+    }
+
+    // the station doesn't have to be on an asteroid, but has to be on the map
+    pub fn vaporize(&self, asteroid_map: &[Vec<char>]) -> Result<Vaporization, String> {
+        let on_map = self.y >= 0
+            && (self.y as usize) < asteroid_map.len()
+            && self.x >= 0
+            && (self.x as usize) < asteroid_map[self.y as usize].len();
+        if !on_map {
+            return Err(format!("Station {},{} is not on the map", self.x, self.y));
+        }
+        let detected = find_all_detections(asteroid_map, self.x, self.y);
+        let directions = self.order(detected.keys().cloned().collect());
+        let mut sequence: Vec<Vaporized> = Vec::new();
+        for (i, direction) in directions.iter().enumerate() {
+            for (rotation, point) in detected[direction].iter().enumerate() {
+                sequence.push(Vaporized {
+                    x: point.x,
+                    y: point.y,
+                    order: i,
+                    rotation: rotation + 1,
+                });
+            }
+        }
+        // every rotation takes the nearest asteroid left in each direction
+        sequence.sort_by_key(|v| (v.rotation, v.order));
+        for (i, v) in sequence.iter_mut().enumerate() {
+            v.order = i + 1;
+        }
+        Ok(Vaporization { sequence })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vaporized {
+    pub x: i32,
+    pub y: i32,
+    // both counted from 1
+    pub order: usize,
+    pub rotation: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vaporization {
+    pub sequence: Vec<Vaporized>,
+}
+impl Vaporization {
+    // the nth asteroid to be vaporized, counted from 1 like the puzzle does
+    pub fn nth(&self, n: usize) -> Option<&Vaporized> {
+        self.sequence.get(n.checked_sub(1)?)
+    }
+
+    pub fn when(&self, x: i32, y: i32) -> Option<&Vaporized> {
+        self.sequence.iter().find(|v| v.x == x && v.y == y)
+    }
+
+    pub fn rotations(&self) -> usize {
+        self.sequence.last().map_or(0, |v| v.rotation)
+    }
+
+    pub fn points(&self) -> Vec<Point> {
+        self.sequence.iter().map(|v| Point::new(v.x, v.y)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Laser, Sweep};
+    use crate::direction::Direction;
+    use crate::parse_map;
+
+    #[test]
+    fn puzzle_sample() {
+        let map = parse_map(include_str!("../test-input5.txt"));
+        let vaporization = Laser::new(11, 13).vaporize(&map).unwrap();
+        assert_eq!(vaporization.sequence.len(), 299);
+        for (n, x, y) in [
+            (1, 11, 12),
+            (2, 12, 1),
+            (3, 12, 2),
+            (10, 12, 8),
+            (20, 16, 0),
+            (50, 16, 9),
+            (100, 10, 16),
+            (199, 9, 6),
+            (200, 8, 2),
+            (201, 10, 9),
+            (299, 11, 1),
+        ] {
+            let v = vaporization.nth(n).unwrap();
+            assert_eq!((v.x, v.y), (x, y), "asteroid {}", n);
+            assert_eq!(vaporization.when(x, y), Some(v));
+        }
+        assert_eq!(vaporization.nth(0), None);
+        assert_eq!(vaporization.nth(300), None);
+        assert_eq!(vaporization.when(11, 13), None);
+        assert_eq!(vaporization.nth(1).unwrap().rotation, 1);
+        assert_eq!(
+            vaporization.nth(299).unwrap().rotation,
+            vaporization.rotations()
+        );
+    }
+
+    #[test]
+    fn rotations_and_sweeps() {
+        let map = parse_map(".#....#####...#..\n##...##.#####..##\n##...#...#.#####.\n..#.....X...###..\n..#.#.....#....##\n");
+        let laser = Laser::new(8, 3);
+        let first: Vec<(i32, i32)> = laser
+            .vaporize(&map)
+            .unwrap()
+            .sequence
+            .iter()
+            .take(9)
+            .map(|v| (v.x, v.y))
+            .collect();
+        assert_eq!(
+            first,
+            vec![
+                (8, 1),
+                (9, 0),
+                (9, 1),
+                (10, 0),
+                (9, 2),
+                (11, 1),
+                (12, 1),
+                (11, 2),
+                (15, 1)
+            ]
+        );
+
+        let counter_clockwise = Laser {
+            sweep: Sweep::CounterClockwise,
+            ..laser.clone()
+        };
+        let first: Vec<(i32, i32)> = counter_clockwise
+            .vaporize(&map)
+            .unwrap()
+            .sequence
+            .iter()
+            .take(3)
+            .map(|v| (v.x, v.y))
+            .collect();
+        assert_eq!(first, vec![(8, 1), (7, 0), (6, 0)]);
+
+        // starting straight right takes the nearest asteroid along it first
+        let right = Laser {
+            start: Direction::new(1, 0).unwrap(),
+            ..laser.clone()
+        };
+        let v = right.vaporize(&map).unwrap();
+        assert_eq!((v.nth(1).unwrap().x, v.nth(1).unwrap().y), (12, 3));
+        assert_eq!(v.nth(2).unwrap().rotation, 1);
+        assert_eq!(v.when(13, 3).unwrap().rotation, 2);
+        // (8,0) is hidden behind (8,1) until the second rotation
+        let far = laser.vaporize(&map).unwrap();
+        assert_eq!(far.when(8, 0).unwrap().rotation, 2);
+    }
+
+    #[test]
+    fn station_off_the_map() {
+        let map = parse_map("#.#\n.#.\n");
+        assert!(Laser::new(3, 0).vaporize(&map).is_err());
+        assert!(Laser::new(0, -1).vaporize(&map).is_err());
+        // a station in empty space still works
+        assert_eq!(Laser::new(1, 0).vaporize(&map).unwrap().sequence.len(), 3);
+    }
+}
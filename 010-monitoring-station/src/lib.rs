@@ -0,0 +1,181 @@
+pub mod direction;
+pub mod gen;
+pub mod laser;
+pub mod visibility;
+
+use direction::Direction;
+use laser::Laser;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub distance: i32,
+}
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y, distance: 0 }
+    }
+}
+
+pub struct AsteroidMap {
+    pub grid: Vec<Vec<char>>,
+}
+
+pub fn parse_input(contents: &str) -> AsteroidMap {
+    AsteroidMap {
+        grid: parse_map(contents),
+    }
+}
+
+pub fn part1(asteroid_map: &AsteroidMap) -> usize {
+    let (_, detection_count) = find_best_detection_location(&mut asteroid_map.grid.clone());
+    detection_count
+}
+
+pub fn part2(asteroid_map: &AsteroidMap) -> i32 {
+    let mut grid = asteroid_map.grid.clone();
+    let (best_location, _) = find_best_detection_location(&mut grid);
+    let vaporization = Laser::new(best_location.x, best_location.y)
+        .vaporize(&grid)
+        .unwrap();
+    let asteroid = vaporization.nth(200).expect("Fewer than 200 asteroids");
+    asteroid.x * 100 + asteroid.y
+}
+
+pub fn destroy_asteroids(asteroid_map: &mut [Vec<char>], laser_location: Point) -> Vec<Point> {
+    Laser::new(laser_location.x, laser_location.y)
+        .vaporize(asteroid_map)
+        .unwrap()
+        .points()
+}
+
+// ties go to the topmost and then the leftmost asteroid
+pub fn find_best_detection_location(asteroid_map: &mut [Vec<char>]) -> (Point, usize) {
+    let best = visibility::ranking(asteroid_map)
+        .into_iter()
+        .next()
+        .expect("No asteroids on the map");
+    (Point::new(best.x, best.y), best.count)
+}
+
+pub fn find_all_detections(
+    asteroid_map: &[Vec<char>],
+    y: i32,
+    x: i32,
+) -> HashMap<Direction, Vec<Point>> {
+    let mut detected_asteroids: HashMap<Direction, Vec<Point>> = HashMap::new();
+    let row_count = asteroid_map.len();
+    let column_count = asteroid_map[0].len();
+    for j in 0..column_count as i32 {
+        for i in 0..row_count as i32 {
+            if x == i && y == j {
+                continue;
+            } else if asteroid_map[i as usize][j as usize] == '#' {
+                // group by direction and order by distance
+                let angle = Direction::between(y, x, j, i).unwrap();
+                let mut curr_point = Point::new(j, i);
+                let curr_distance =
+                    get_distance_between_points(Point::new(y, x), curr_point.clone());
+                curr_point.distance = curr_distance;
+                if let Some(array) = detected_asteroids.get_mut(&angle) {
+                    let mut index = 0;
+                    while index < array.len() && array[index].distance < curr_distance {
+                        index += 1;
+                    }
+                    array.insert(index, curr_point);
+                } else {
+                    detected_asteroids.insert(angle, vec![curr_point]);
+                }
+            }
+        }
+    }
+
+    detected_asteroids
+}
+
+pub fn get_distance_between_points(p1: Point, p2: Point) -> i32 {
+    (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
+}
+
+pub fn parse_map(contents: &str) -> Vec<Vec<char>> {
+    let asteroid_map: Vec<Vec<char>> = contents
+        .split_terminator('\n')
+        .map(|r| {
+            let chars = r.chars();
+            let mut row = Vec::new();
+            for ch in chars {
+                row.push(ch);
+            }
+            row
+        })
+        .collect();
+    asteroid_map
+}
+
+pub fn print_map(map: &mut [Vec<char>]) {
+    let w = map.len();
+    let h = map[0].len();
+    let mut sb = String::new();
+    for row in map.iter().take(w) {
+        for &cell in row.iter().take(h) {
+            sb.push(cell);
+        }
+        sb.push('\n');
+    }
+    println!("{}", sb)
+}
+
+#[cfg(test)]
+mod test {
+    use super::destroy_asteroids;
+    use super::find_best_detection_location;
+    use super::parse_map;
+
+    #[test]
+    fn part1_sample_input1() {
+        let mut map = parse_map(include_str!("../test-input.txt"));
+        let (location, _) = find_best_detection_location(&mut map);
+        assert_eq!(vec![location.x, location.y], [3, 4]);
+    }
+
+    #[test]
+    fn part1_sample_input2() {
+        let mut map = parse_map(include_str!("../test-input2.txt"));
+        let (location, _) = find_best_detection_location(&mut map);
+        assert_eq!(vec![location.x, location.y], [5, 8]);
+    }
+
+    #[test]
+    fn part1_sample_input3() {
+        let mut map = parse_map(include_str!("../test-input3.txt"));
+        let (location, _) = find_best_detection_location(&mut map);
+        assert_eq!(vec![location.x, location.y], [1, 2]);
+    }
+
+    #[test]
+    fn part1_sample_input4() {
+        let mut map = parse_map(include_str!("../test-input4.txt"));
+        let (location, _) = find_best_detection_location(&mut map);
+        assert_eq!(vec![location.x, location.y], [6, 3]);
+    }
+
+    #[test]
+    fn part1_sample_input5() {
+        let mut map = parse_map(include_str!("../test-input5.txt"));
+        let (location, _) = find_best_detection_location(&mut map);
+        assert_eq!(vec![location.x, location.y], [11, 13]);
+    }
+
+    #[test]
+    fn part2_sample_input1() {
+        let mut map = parse_map(include_str!("../test-input5.txt"));
+        let (location, _) = find_best_detection_location(&mut map);
+        let vaporized_asteroids = destroy_asteroids(&mut map, location);
+        assert_eq!(
+            vec![vaporized_asteroids[199].x, vaporized_asteroids[199].y],
+            [8, 2]
+        );
+    }
+}
@@ -1,208 +1,129 @@
-use std::collections::HashMap;
-use std::f64;
-use std::fs::File;
-use std::io::prelude::*;
-
-#[derive(Clone, Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-    distance: i32,
-}
-impl Point {
-    pub fn new(x: i32, y: i32) -> Point {
-        Point { x, y, distance: 0 }
-    }
-}
+use aoc_bench::{gen_arg, input_source, read_input, take_option, Bench};
+use monitoring_station::direction::Direction;
+use monitoring_station::laser::{Laser, Sweep, Vaporized};
+use monitoring_station::visibility;
+use monitoring_station::{find_best_detection_location, gen, parse_input, part1, part2};
+use std::env;
 
 fn main() {
-    let mut asteroid_map = load_map(String::from("input.txt"));
-
-    let (best_location, detection_count) = find_best_detection_location(&mut asteroid_map);
-
-    println!("Monitoring Station part1 Solution: {:?}", detection_count);
-
-    let vaporized_asteroids = destroy_asteroids(&mut asteroid_map, best_location);
-    println!(
-        "Monitoring Station part2 Solution: {}",
-        vaporized_asteroids[199].x * 100 + vaporized_asteroids[199].y
-    );
-}
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "gen" {
+        let seed = gen_arg(&args, 2, 0);
+        let width = gen_arg(&args, 3, 33);
+        let height = gen_arg(&args, 4, 33);
+        let asteroids = gen_arg(&args, 5, 400);
+        print!(
+            "{}",
+            gen::asteroid_field(seed as u64, width, height, asteroids)
+        );
+        return;
+    }
 
-fn destroy_asteroids(asteroid_map: &mut Vec<Vec<char>>, laser_location: Point) -> Vec<Point> {
-    let mut detected_asteroids =
-        find_all_detections(asteroid_map, laser_location.x, laser_location.y);
-    let mut vaporized_asteroids = Vec::new();
-    let mut curr_rotation;
-    let mut angles: Vec<String> = detected_asteroids.keys().cloned().collect();
-    angles.sort_by(|a, b| {
-        return a
-            .parse::<f64>()
-            .unwrap()
-            .partial_cmp(&b.parse::<f64>().unwrap())
-            .unwrap();
-    });
-    let mut i = angles.len() - 1;
+    if args.len() > 1 && args[1] == "bench" {
+        let mut bench = Bench::from_args(&args[2..]);
+        let contents = read_input(&bench.input);
+        let input = parse_input(&contents);
+        bench.time("parse", || parse_input(&contents));
+        bench.time("part1", || part1(&input));
+        bench.time("part2", || part2(&input));
+        bench.finish();
+        return;
+    }
 
-    while !detected_asteroids.is_empty() {
-        curr_rotation = angles[i].clone();
-        if let Some(array) = detected_asteroids.get_mut(&curr_rotation) {
-            if array.len() > 0 {
-                let item = array.remove(0);
-                vaporized_asteroids.push(item);
+    // rank [input] [--top <n>] lists the asteroids by how many others they see,
+    // heatmap [input] [--svg] draws the counts on the map
+    if args.len() > 1 && (args[1] == "rank" || args[1] == "heatmap") {
+        let mut rest: Vec<String> = args[2..].to_vec();
+        let top = take_option(&mut rest, "--top", 1);
+        let svg = take_option(&mut rest, "--svg", 0).is_some();
+        let source = input_source(rest.first().cloned());
+        let grid = parse_input(&read_input(&source)).grid;
+        if args[1] == "rank" {
+            let ranking = visibility::ranking(&grid);
+            let top = top.map_or(ranking.len(), |t| t[0].parse().expect("Invalid --top"));
+            println!("{:>5}{:>5}{:>5}{:>7}", "rank", "x", "y", "sees");
+            for (i, v) in ranking.iter().take(top).enumerate() {
+                println!("{:>5}{:>5}{:>5}{:>7}", i + 1, v.x, v.y, v.count);
             }
-            if array.len() == 0 {
-                detected_asteroids.remove(&curr_rotation);
+        } else {
+            let counts = visibility::visibility_counts(&grid);
+            if svg {
+                println!("{}", visibility::heatmap_svg(&grid, &counts));
+            } else {
+                println!("{}", visibility::heatmap(&grid, &counts));
             }
         }
-        i = (i + angles.len() - 1) % angles.len();
+        return;
     }
 
-    vaporized_asteroids
-}
-
-fn find_best_detection_location(asteroid_map: &mut Vec<Vec<char>>) -> (Point, usize) {
-    let row_count = asteroid_map.len();
-    let column_count = asteroid_map[0].len();
-    let mut most_detection_counts = 0;
-    let mut best_position = None;
-    for y in 0..column_count {
-        for x in 0..row_count {
-            if asteroid_map[x][y] == '#' {
-                let detection_count = find_all_detections(asteroid_map, y as i32, x as i32)
-                    .keys()
-                    .len();
-                if detection_count > most_detection_counts {
-                    most_detection_counts = detection_count;
-                    // creating (y,x) point
-                    best_position = Some(Point::new(y as i32, x as i32));
-                }
+    // vaporize [input] [--station <x> <y>] [--counter-clockwise] [--start <dx> <dy>]
+    //          [--nth <n>] [--at <x> <y>]
+    // prints the whole vaporization order, or only the asked for asteroids
+    if args.len() > 1 && args[1] == "vaporize" {
+        let mut rest: Vec<String> = args[2..].to_vec();
+        let station = take_option(&mut rest, "--station", 2);
+        let counter_clockwise = take_option(&mut rest, "--counter-clockwise", 0).is_some();
+        let start = take_option(&mut rest, "--start", 2);
+        let nth = take_option(&mut rest, "--nth", 1);
+        let at = take_option(&mut rest, "--at", 2);
+        let source = input_source(rest.first().cloned());
+        let mut grid = parse_input(&read_input(&source)).grid;
+        let mut laser = match station {
+            Some(station) => Laser::new(coord_arg(&station[0]), coord_arg(&station[1])),
+            None => {
+                let (best, _) = find_best_detection_location(&mut grid);
+                Laser::new(best.x, best.y)
             }
+        };
+        if counter_clockwise {
+            laser.sweep = Sweep::CounterClockwise;
         }
-    }
-    (best_position.unwrap(), most_detection_counts)
-}
-
-fn find_all_detections(
-    asteroid_map: &mut Vec<Vec<char>>,
-    y: i32,
-    x: i32,
-) -> HashMap<String, Vec<Point>> {
-    let mut detected_asteroids: HashMap<String, Vec<Point>> = HashMap::new();
-    let row_count = asteroid_map.len();
-    let column_count = asteroid_map[0].len();
-    for j in 0..column_count as i32 {
-        for i in 0..row_count as i32 {
-            if x == i && y == j {
-                continue;
-            } else if asteroid_map[i as usize][j as usize] == '#' {
-                // calculate angle, put into map and order by distance
-                let angle = ((j - y) as f64).atan2((i - x) as f64).to_string();
-                let mut curr_point = Point::new(j, i);
-                let curr_distance =
-                    get_distance_between_points(Point::new(y, x), curr_point.clone());
-                curr_point.distance = curr_distance;
-                if let Some(array) = detected_asteroids.get_mut(&angle) {
-                    let mut index = 0;
-                    while index < array.len() && array[index].distance < curr_distance {
-                        index += 1;
-                    }
-                    array.insert(index, curr_point);
-                } else {
-                    detected_asteroids.insert(angle, vec![curr_point]);
-                }
+        if let Some(start) = start {
+            laser.start = Direction::new(coord_arg(&start[0]), coord_arg(&start[1]))
+                .expect("The start direction can't be 0,0");
+        }
+        let vaporization = laser.vaporize(&grid).unwrap_or_else(|e| panic!("{}", e));
+        println!("station {},{}", laser.x, laser.y);
+        let print = |v: &Vaporized| {
+            println!(
+                "{:>5} {:>3},{:<3} rotation {}",
+                v.order, v.x, v.y, v.rotation
+            )
+        };
+        if nth.is_none() && at.is_none() {
+            vaporization.sequence.iter().for_each(print);
+        }
+        if let Some(nth) = nth {
+            match vaporization.nth(nth[0].parse().expect("Invalid --nth")) {
+                Some(v) => print(v),
+                None => println!("there are only {} asteroids", vaporization.sequence.len()),
             }
         }
-    }
-
-    detected_asteroids
-}
-
-fn get_distance_between_points(p1: Point, p2: Point) -> i32 {
-    (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
-}
-
-fn load_map(filename: String) -> Vec<Vec<char>> {
-    let mut file = File::open(filename).expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Failed to read input file");
-    let asteroid_map: Vec<Vec<char>> = contents
-        .split_terminator('\n')
-        .map(|r| {
-            let mut chars = r.chars();
-            let mut row = Vec::new();
-            while let Some(ch) = chars.next() {
-                row.push(ch);
+        if let Some(at) = at {
+            let (x, y) = (coord_arg(&at[0]), coord_arg(&at[1]));
+            match vaporization.when(x, y) {
+                Some(v) => print(v),
+                None => println!("{},{} is never vaporized", x, y),
             }
-            row
-        })
-        .collect();
-    asteroid_map
-}
-
-fn print_map(map: &mut Vec<Vec<char>>) {
-    let w = map.len();
-    let h = map[0].len();
-    let mut sb = String::new();
-    for i in 0..w {
-        for j in 0..h {
-            sb.push(map[i][j]);
         }
-        sb.push('\n');
-    }
-    println!("{}", sb)
-}
-
-#[cfg(test)]
-mod test {
-    use super::destroy_asteroids;
-    use super::find_best_detection_location;
-    use super::load_map;
-
-    #[test]
-    fn part1_sample_input1() {
-        let mut map = load_map(String::from("test-input.txt"));
-        let (location, _) = find_best_detection_location(&mut map);
-        assert_eq!(vec![location.x, location.y], [3, 4]);
+        return;
     }
 
-    #[test]
-    fn part1_sample_input2() {
-        let mut map = load_map(String::from("test-input2.txt"));
-        let (location, _) = find_best_detection_location(&mut map);
-        assert_eq!(vec![location.x, location.y], [5, 8]);
-    }
+    let source = input_source(env::args().nth(1));
+    let contents = read_input(&source);
+    let asteroid_map = parse_input(&contents);
 
-    #[test]
-    fn part1_sample_input3() {
-        let mut map = load_map(String::from("test-input3.txt"));
-        let (location, _) = find_best_detection_location(&mut map);
-        assert_eq!(vec![location.x, location.y], [1, 2]);
-    }
-
-    #[test]
-    fn part1_sample_input4() {
-        let mut map = load_map(String::from("test-input4.txt"));
-        let (location, _) = find_best_detection_location(&mut map);
-        assert_eq!(vec![location.x, location.y], [6, 3]);
-    }
+    println!(
+        "Monitoring Station part1 Solution: {:?}",
+        part1(&asteroid_map)
+    );
 
-    #[test]
-    fn part1_sample_input5() {
-        let mut map = load_map(String::from("test-input5.txt"));
-        let (location, _) = find_best_detection_location(&mut map);
-        assert_eq!(vec![location.x, location.y], [11, 13]);
-    }
+    println!(
+        "Monitoring Station part2 Solution: {}",
+        part2(&asteroid_map)
+    );
+}
 
-    #[test]
-    fn part2_sample_input1() {
-        let mut map = load_map(String::from("test-input5.txt"));
-        let (location, _) = find_best_detection_location(&mut map);
-        let vaporized_asteroids = destroy_asteroids(&mut map, location);
-        assert_eq!(
-            vec![vaporized_asteroids[199].x, vaporized_asteroids[199].y],
-            [8, 2]
-        );
-    }
+fn coord_arg(arg: &str) -> i32 {
+    arg.parse().expect("Invalid coordinate")
 }
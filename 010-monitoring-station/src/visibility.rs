@@ -0,0 +1,166 @@
+use crate::direction::Direction;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Visibility {
+    pub x: i32,
+    pub y: i32,
+    // asteroids that can be seen from this one
+    pub count: usize,
+}
+
+pub fn asteroids(asteroid_map: &[Vec<char>]) -> Vec<(i32, i32)> {
+    let mut res = Vec::new();
+    for (y, row) in asteroid_map.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            if c == '#' {
+                res.push((x as i32, y as i32));
+            }
+        }
+    }
+    res
+}
+
+// every asteroid in reading order, the O(n²) pass split across threads
+pub fn visibility_counts(asteroid_map: &[Vec<char>]) -> Vec<Visibility> {
+    let asteroids = asteroids(asteroid_map);
+    asteroids
+        .par_iter()
+        .map(|&(x, y)| {
+            let directions: HashSet<Direction> = asteroids
+                .iter()
+                .filter_map(|&(to_x, to_y)| Direction::between(x, y, to_x, to_y))
+                .collect();
+            Visibility {
+                x,
+                y,
+                count: directions.len(),
+            }
+        })
+        .collect()
+}
+
+// the best stations first, ties go to the topmost and then the leftmost
+pub fn ranking(asteroid_map: &[Vec<char>]) -> Vec<Visibility> {
+    let mut ranking = visibility_counts(asteroid_map);
+    ranking.sort_by_key(|v| (Reverse(v.count), v.y, v.x));
+    ranking
+}
+
+// the map with every asteroid replaced by its count
+pub fn heatmap(asteroid_map: &[Vec<char>], counts: &[Visibility]) -> String {
+    let max = counts.iter().map(|v| v.count).max().unwrap_or(0);
+    let width = max.to_string().len() + 1;
+    let mut grid: Vec<Vec<String>> = asteroid_map
+        .iter()
+        .map(|row| row.iter().map(|_| format!("{:>1$}", ".", width)).collect())
+        .collect();
+    for v in counts {
+        grid[v.y as usize][v.x as usize] = format!("{:>1$}", v.count, width);
+    }
+    grid.iter()
+        .map(|row| row.concat())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn heatmap_svg(asteroid_map: &[Vec<char>], counts: &[Visibility]) -> String {
+    let height = asteroid_map.len();
+    let width = asteroid_map.iter().map(|row| row.len()).max().unwrap_or(0);
+    let min = counts.iter().map(|v| v.count).min().unwrap_or(0);
+    let max = counts.iter().map(|v| v.count).max().unwrap_or(0);
+    let best = counts
+        .iter()
+        .min_by_key(|v| (Reverse(v.count), v.y, v.x))
+        .cloned();
+    let mut lines = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">",
+        width * 10,
+        height * 10
+    )];
+    lines.push(String::from(
+        "<rect x=\"0\" y=\"0\" width=\"100%\" height=\"100%\" fill=\"black\"/>",
+    ));
+    for v in counts {
+        // dark blue for the fewest asteroids seen up to yellow for the most
+        let t = (v.count - min) as f64 / (max - min).max(1) as f64;
+        let colour = format!(
+            "rgb({},{},{})",
+            (40.0 + 215.0 * t) as u8,
+            (20.0 + 210.0 * t) as u8,
+            (120.0 - 100.0 * t) as u8
+        );
+        let stroke = if best.as_ref() == Some(v) {
+            " stroke=\"white\" stroke-width=\"2\""
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"10\" height=\"10\" fill=\"{}\"{}><title>({}, {}) sees {}</title></rect>",
+            v.x * 10,
+            v.y * 10,
+            colour,
+            stroke,
+            v.x,
+            v.y,
+            v.count
+        ));
+    }
+    lines.push(String::from("</svg>"));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{heatmap, heatmap_svg, ranking, visibility_counts, Visibility};
+    use crate::{find_all_detections, gen, parse_map};
+
+    #[test]
+    fn sample_counts() {
+        let map = parse_map(include_str!("../test-input.txt"));
+        let counts = visibility_counts(&map);
+        assert_eq!(counts.len(), 10);
+        assert_eq!(
+            heatmap(&map, &counts),
+            " . 7 . . 7\n . . . . .\n 6 7 7 7 5\n . . . . 7\n . . . 8 7"
+        );
+        assert_eq!(
+            ranking(&map)[0],
+            Visibility {
+                x: 3,
+                y: 4,
+                count: 8
+            }
+        );
+    }
+
+    #[test]
+    fn ties_go_to_the_topmost_then_leftmost() {
+        let map = parse_map("#.#\n...\n#.#\n");
+        let ranking = ranking(&map);
+        assert!(ranking.iter().all(|v| v.count == 3));
+        let order: Vec<(i32, i32)> = ranking.iter().map(|v| (v.x, v.y)).collect();
+        assert_eq!(order, vec![(0, 0), (2, 0), (0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn matches_the_detections() {
+        let map = parse_map(&gen::asteroid_field(3, 25, 20, 150));
+        for v in visibility_counts(&map) {
+            assert_eq!(v.count, find_all_detections(&map, v.x, v.y).len());
+        }
+    }
+
+    #[test]
+    fn svg_marks_the_best_station() {
+        let map = parse_map(include_str!("../test-input.txt"));
+        let svg = heatmap_svg(&map, &visibility_counts(&map));
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 10);
+        assert_eq!(svg.matches("stroke=\"white\"").count(), 1);
+        assert!(svg.contains("(3, 4) sees 8"));
+    }
+}
@@ -0,0 +1,345 @@
+use crate::{compute, Program};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+impl Direction {
+    pub fn left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    // y grows downwards, like the registration is printed
+    pub fn step(self) -> (i128, i128) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Black,
+    White,
+}
+impl Colour {
+    pub fn to_char(self) -> char {
+        match self {
+            Colour::Black => '.',
+            Colour::White => '#',
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+// whatever tells the robot what to do, given the colour under its camera
+pub trait Brain {
+    fn next(&mut self, camera: Colour) -> Option<(Colour, Turn)>;
+}
+
+// the puzzle's brain, an intcode program reading 0 or 1 for the colour and
+// answering with the colour to paint and 0 to turn left or 1 to turn right
+pub struct IntcodeBrain {
+    memory: HashMap<i128, i128>,
+    inputs: Vec<i128>,
+    op_pos: i128,
+    rel_pos: i128,
+    input_pos: usize,
+    halted: bool,
+}
+impl IntcodeBrain {
+    pub fn new(program: &Program) -> IntcodeBrain {
+        IntcodeBrain {
+            memory: program.memory.clone(),
+            inputs: Vec::new(),
+            op_pos: 0,
+            rel_pos: 0,
+            input_pos: 0,
+            halted: false,
+        }
+    }
+}
+impl Brain for IntcodeBrain {
+    fn next(&mut self, camera: Colour) -> Option<(Colour, Turn)> {
+        if self.halted {
+            return None;
+        }
+        self.inputs.push(match camera {
+            Colour::Black => 0,
+            Colour::White => 1,
+        });
+        let (output, op_pos, rel_pos, input_pos, op_code) = compute(
+            &mut self.memory,
+            &self.inputs,
+            self.op_pos,
+            self.rel_pos,
+            self.input_pos,
+        );
+        self.op_pos = op_pos;
+        self.rel_pos = rel_pos;
+        self.input_pos = input_pos;
+        self.halted = op_code == 99;
+        if output.len() < 2 {
+            return None;
+        }
+        let colour = if output[0] == 1 {
+            Colour::White
+        } else {
+            Colour::Black
+        };
+        let turn = if output[1] == 1 {
+            Turn::Right
+        } else {
+            Turn::Left
+        };
+        Some((colour, turn))
+    }
+}
+
+// a brain following a fixed list of instructions, whatever the camera sees
+pub struct Script {
+    pub instructions: Vec<(Colour, Turn)>,
+    next: usize,
+}
+impl Script {
+    pub fn new(instructions: Vec<(Colour, Turn)>) -> Script {
+        Script {
+            instructions,
+            next: 0,
+        }
+    }
+}
+impl Brain for Script {
+    fn next(&mut self, _camera: Colour) -> Option<(Colour, Turn)> {
+        let instruction = self.instructions.get(self.next).copied();
+        self.next += 1;
+        instruction
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PaintEvent {
+    // counted from 0, one step per instruction of the brain
+    pub step: usize,
+    pub x: i128,
+    pub y: i128,
+    pub colour: Colour,
+}
+
+#[derive(Clone, Debug)]
+pub struct Robot {
+    pub x: i128,
+    pub y: i128,
+    pub facing: Direction,
+    // panels never painted are black, unless they start out otherwise
+    pub panels: HashMap<(i128, i128), Colour>,
+    pub visited: HashSet<(i128, i128)>,
+    pub events: Vec<PaintEvent>,
+}
+impl Robot {
+    pub fn new(x: i128, y: i128, facing: Direction) -> Robot {
+        let mut visited = HashSet::new();
+        visited.insert((x, y));
+        Robot {
+            x,
+            y,
+            facing,
+            panels: HashMap::new(),
+            visited,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn camera(&self) -> Colour {
+        self.panels
+            .get(&(self.x, self.y))
+            .copied()
+            .unwrap_or(Colour::Black)
+    }
+
+    pub fn paint(&mut self, colour: Colour) {
+        self.events.push(PaintEvent {
+            step: self.events.len(),
+            x: self.x,
+            y: self.y,
+            colour,
+        });
+        self.panels.insert((self.x, self.y), colour);
+    }
+
+    pub fn turn_and_move(&mut self, turn: Turn) {
+        self.facing = match turn {
+            Turn::Left => self.facing.left(),
+            Turn::Right => self.facing.right(),
+        };
+        let (dx, dy) = self.facing.step();
+        self.x += dx;
+        self.y += dy;
+        self.visited.insert((self.x, self.y));
+    }
+
+    // until the brain has nothing more to say
+    pub fn run(&mut self, brain: &mut dyn Brain) {
+        while let Some((colour, turn)) = brain.next(self.camera()) {
+            self.paint(colour);
+            self.turn_and_move(turn);
+        }
+    }
+
+    pub fn painted_count(&self) -> usize {
+        self.events
+            .iter()
+            .map(|e| (e.x, e.y))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    pub fn visited_count(&self) -> usize {
+        self.visited.len()
+    }
+}
+
+// the hull after every `every` paint events and after the last one, each
+// frame covering the whole painted area so they play back in place, with @
+// on the panel just painted
+pub fn frames(
+    events: &[PaintEvent],
+    start: &HashMap<(i128, i128), Colour>,
+    every: usize,
+) -> Vec<String> {
+    let every = every.max(1);
+    let positions: Vec<(i128, i128)> = events
+        .iter()
+        .map(|e| (e.x, e.y))
+        .chain(start.keys().copied())
+        .collect();
+    if positions.is_empty() {
+        return Vec::new();
+    }
+    let min_x = positions.iter().map(|p| p.0).min().unwrap();
+    let max_x = positions.iter().map(|p| p.0).max().unwrap();
+    let min_y = positions.iter().map(|p| p.1).min().unwrap();
+    let max_y = positions.iter().map(|p| p.1).max().unwrap();
+
+    let mut panels = start.clone();
+    let mut frames = Vec::new();
+    for (i, event) in events.iter().enumerate() {
+        panels.insert((event.x, event.y), event.colour);
+        if (i + 1) % every == 0 || i + 1 == events.len() {
+            let mut frame = String::new();
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    if (x, y) == (event.x, event.y) {
+                        frame.push('@');
+                    } else {
+                        let colour = panels.get(&(x, y)).copied().unwrap_or(Colour::Black);
+                        frame.push(colour.to_char());
+                    }
+                }
+                frame.push('\n');
+            }
+            frames.push(frame);
+        }
+    }
+    frames
+}
+
+#[cfg(test)]
+mod test {
+    use super::{frames, Colour, Direction, IntcodeBrain, Robot, Script, Turn};
+    use crate::parse_input;
+
+    fn sample() -> Robot {
+        let mut robot = Robot::new(0, 0, Direction::Up);
+        robot.run(&mut Script::new(vec![
+            (Colour::White, Turn::Left),
+            (Colour::Black, Turn::Left),
+            (Colour::White, Turn::Left),
+            (Colour::White, Turn::Left),
+            (Colour::Black, Turn::Right),
+            (Colour::White, Turn::Left),
+            (Colour::White, Turn::Left),
+        ]));
+        robot
+    }
+
+    #[test]
+    fn directions() {
+        let mut facing = Direction::Up;
+        for expected in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ] {
+            facing = facing.right();
+            assert_eq!(facing, expected);
+            assert_eq!(facing.right().left(), facing);
+        }
+        assert_eq!(Direction::Left.step(), (-1, 0));
+    }
+
+    #[test]
+    fn puzzle_sample() {
+        let robot = sample();
+        assert_eq!(robot.painted_count(), 6);
+        // the robot ends up on a panel it never paints
+        assert_eq!(robot.visited_count(), 7);
+        assert_eq!((robot.x, robot.y, robot.facing), (0, -1, Direction::Left));
+        assert_eq!(robot.events.len(), 7);
+        assert_eq!(robot.events[4].step, 4);
+        assert_eq!((robot.events[4].x, robot.events[4].y), (0, 0));
+        assert_eq!(robot.events[4].colour, Colour::Black);
+        let white = robot
+            .panels
+            .values()
+            .filter(|&&c| c == Colour::White)
+            .count();
+        assert_eq!(white, 4);
+    }
+
+    #[test]
+    fn animation_frames() {
+        let robot = sample();
+        let frames = frames(&robot.events, &Default::default(), 3);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0], "...\n.#.\n@..\n");
+        assert_eq!(frames[2], "..@\n..#\n##.\n");
+        assert!(frames.iter().all(|f| f.len() == frames[0].len()));
+    }
+
+    #[test]
+    fn puzzle_robot() {
+        let program = parse_input(include_str!("../input.txt"));
+        let mut robot = Robot::new(0, 0, Direction::Up);
+        robot.run(&mut IntcodeBrain::new(&program));
+        assert!(robot.visited_count() >= robot.painted_count());
+        assert_eq!(robot.painted_count(), 2018);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::system::{Axis, System};
+use rayon::prelude::*;
+
+// one axis with positions and velocities side by side in a single buffer
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackedAxis {
+    bodies: usize,
+    // every position, then every velocity
+    state: Vec<i64>,
+}
+impl PackedAxis {
+    pub fn new(axis: &Axis) -> PackedAxis {
+        let mut state = axis.positions.clone();
+        state.extend(axis.velocities.iter());
+        PackedAxis {
+            bodies: axis.positions.len(),
+            state,
+        }
+    }
+
+    pub fn positions(&self) -> &[i64] {
+        &self.state[..self.bodies]
+    }
+
+    pub fn velocities(&self) -> &[i64] {
+        &self.state[self.bodies..]
+    }
+
+    pub fn step(&mut self) {
+        let (positions, velocities) = self.state.split_at_mut(self.bodies);
+        for i in 0..positions.len() {
+            let mut pull = 0;
+            for &other in positions.iter() {
+                pull += (other - positions[i]).signum();
+            }
+            velocities[i] += pull;
+        }
+        for (position, velocity) in positions.iter_mut().zip(velocities.iter()) {
+            *position += velocity;
+        }
+    }
+
+    // the step is reversible and a state with no velocity is its own mirror
+    // image, so an axis starting at rest comes back to rest halfway through
+    // its cycle: with all velocities 0 after k steps for an even cycle of 2k,
+    // or with every velocity flipped between steps k-1 and k for an odd one
+    // of 2k-1. only velocities get compared, and only half the cycle is run
+    pub fn find_cycle(&self) -> u64 {
+        if self.velocities().iter().any(|&v| v != 0) {
+            return Axis {
+                positions: self.positions().to_vec(),
+                velocities: self.velocities().to_vec(),
+            }
+            .find_cycle();
+        }
+        let mut axis = self.clone();
+        let mut previous = axis.velocities().to_vec();
+        let mut steps = 0;
+        loop {
+            axis.step();
+            steps += 1;
+            let velocities = axis.velocities();
+            if velocities
+                .iter()
+                .zip(previous.iter())
+                .all(|(v, p)| *v == -p)
+            {
+                return 2 * steps - 1;
+            }
+            if velocities.iter().all(|&v| v == 0) {
+                return 2 * steps;
+            }
+            previous.copy_from_slice(velocities);
+        }
+    }
+}
+
+// every axis on a thread of its own
+pub fn find_cycles(system: &System) -> Vec<u64> {
+    system
+        .axes
+        .par_iter()
+        .map(|axis| PackedAxis::new(axis).find_cycle())
+        .collect()
+}
+
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let tmp = a;
+        a = b;
+        b = tmp % b;
+    }
+    a
+}
+
+// none when it doesn't fit
+pub fn lcm(a: u128, b: u128) -> Option<u128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub fn lcm_of(cycles: &[u64]) -> Option<u128> {
+    cycles
+        .iter()
+        .try_fold(1, |acc, &cycle| lcm(acc, cycle as u128))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_cycles, lcm, lcm_of, PackedAxis};
+    use crate::parse_input;
+    use crate::system::Axis;
+
+    #[test]
+    fn matches_the_full_simulation() {
+        let mut seed: u64 = 12;
+        for bodies in 2..5 {
+            for _ in 0..20 {
+                let positions: Vec<i64> = (0..bodies)
+                    .map(|_| {
+                        seed = seed
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        (seed >> 33) as i64 % 11 - 5
+                    })
+                    .collect();
+                let axis = Axis::new(positions);
+                assert_eq!(
+                    PackedAxis::new(&axis).find_cycle(),
+                    axis.find_cycle(),
+                    "{:?}",
+                    axis
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn odd_cycles() {
+        let axis = Axis::new(vec![5, 0, 3]);
+        assert_eq!(axis.find_cycle() % 2, 1);
+        assert_eq!(PackedAxis::new(&axis).find_cycle(), axis.find_cycle());
+        // a single body never moves
+        assert_eq!(PackedAxis::new(&Axis::new(vec![7])).find_cycle(), 1);
+    }
+
+    #[test]
+    fn moving_start() {
+        let mut axis = Axis::new(vec![0, 3, 1]);
+        axis.velocities = vec![1, 0, -1];
+        assert_eq!(PackedAxis::new(&axis).find_cycle(), axis.find_cycle());
+    }
+
+    #[test]
+    fn checked_lcm() {
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm_of(&[161428, 231614, 102356]), Some(478373365921244));
+        assert_eq!(lcm_of(&[]), Some(1));
+        assert_eq!(lcm(u128::MAX, 2), None);
+        assert_eq!(lcm_of(&[u64::MAX, u64::MAX - 1, u64::MAX - 2]), None);
+    }
+
+    #[test]
+    fn puzzle_cycles() {
+        let system = parse_input(include_str!("../input.txt")).system;
+        assert_eq!(find_cycles(&system), vec![161428, 231614, 102356]);
+    }
+}
@@ -0,0 +1,111 @@
+pub mod cycles;
+pub mod system;
+pub mod timeline;
+
+use system::{parse_bodies, System};
+
+pub struct Moons {
+    pub system: System,
+}
+
+pub fn parse_input(contents: &str) -> Moons {
+    let bodies = parse_bodies(contents).unwrap_or_else(|e| panic!("{}", e));
+    Moons {
+        system: System::new(&bodies).unwrap_or_else(|e| panic!("{}", e)),
+    }
+}
+
+pub fn part1(moons: &Moons) -> i64 {
+    calculate_total_energy(&mut moons.system.clone(), 1000)
+}
+
+pub fn part2(moons: &Moons) -> u128 {
+    calculate_steps_to_repeating(&moons.system).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn calculate_steps_to_repeating(system: &System) -> Result<u128, String> {
+    let cycles = find_cycles(system);
+    cycles::lcm_of(&cycles)
+        .ok_or_else(|| format!("The cycles {:?} repeat after more than 2^128 steps", cycles))
+}
+
+// steps until every axis is back where it started, axis by axis
+pub fn find_cycles(system: &System) -> Vec<u64> {
+    cycles::find_cycles(system)
+}
+
+pub fn calculate_total_energy(system: &mut System, steps: usize) -> i64 {
+    system.run(steps);
+    system.total_energy()
+}
+
+#[cfg(test)]
+mod test {
+    use super::calculate_steps_to_repeating;
+    use super::calculate_total_energy;
+    use super::system::System;
+
+    #[test]
+    fn part1_sample_input1() {
+        let mut system = System::new(&[
+            vec![-1, 0, 2],
+            vec![2, -10, -7],
+            vec![4, -8, 8],
+            vec![3, 5, -1],
+        ])
+        .unwrap();
+
+        let energy = calculate_total_energy(&mut system, 10);
+        assert_eq!(system.position(0), vec![2, 1, -3]);
+        assert_eq!(system.position(1), vec![1, -8, 0]);
+        assert_eq!(system.position(2), vec![3, -6, 1]);
+        assert_eq!(system.position(3), vec![2, 0, 4]);
+        assert_eq!(energy, 179);
+    }
+
+    #[test]
+    fn part1_sample_input2() {
+        let mut system = System::new(&[
+            vec![-8, -10, 0],
+            vec![5, 5, 10],
+            vec![2, -7, 3],
+            vec![9, -8, -3],
+        ])
+        .unwrap();
+        let energy = calculate_total_energy(&mut system, 100);
+
+        assert_eq!(system.position(0), vec![8, -12, -9]);
+        assert_eq!(system.position(1), vec![13, 16, -3]);
+        assert_eq!(system.position(2), vec![-29, -11, -1]);
+        assert_eq!(system.position(3), vec![16, -13, 23]);
+        assert_eq!(energy, 1940);
+    }
+
+    #[test]
+    fn part2_sample_input1() {
+        let system = System::new(&[
+            vec![-1, 0, 2],
+            vec![2, -10, -7],
+            vec![4, -8, 8],
+            vec![3, 5, -1],
+        ])
+        .unwrap();
+
+        let steps = calculate_steps_to_repeating(&system);
+        assert_eq!(steps, Ok(2772));
+    }
+
+    #[test]
+    fn part2_sample_input2() {
+        let system = System::new(&[
+            vec![-8, -10, 0],
+            vec![5, 5, 10],
+            vec![2, -7, 3],
+            vec![9, -8, -3],
+        ])
+        .unwrap();
+        let steps = calculate_steps_to_repeating(&system);
+
+        assert_eq!(steps, Ok(4686774924));
+    }
+}
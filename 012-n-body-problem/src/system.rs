@@ -0,0 +1,278 @@
+// one axis of every body, axes don't affect each other so each one can be
+// simulated on its own
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Axis {
+    pub positions: Vec<i64>,
+    pub velocities: Vec<i64>,
+}
+impl Axis {
+    pub fn new(positions: Vec<i64>) -> Axis {
+        let velocities = vec![0; positions.len()];
+        Axis {
+            positions,
+            velocities,
+        }
+    }
+
+    pub fn apply_gravity(&mut self) {
+        for i in 0..self.positions.len() {
+            for j in 0..self.positions.len() {
+                self.velocities[i] += (self.positions[j] - self.positions[i]).signum();
+            }
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.apply_gravity();
+        for (position, velocity) in self.positions.iter_mut().zip(self.velocities.iter()) {
+            *position += velocity;
+        }
+    }
+
+    // steps until the axis is back where it started
+    pub fn find_cycle(&self) -> u64 {
+        let mut axis = self.clone();
+        let mut steps = 0;
+        loop {
+            axis.step();
+            steps += 1;
+            if axis == *self {
+                return steps;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct System {
+    pub axes: Vec<Axis>,
+}
+impl System {
+    // one position per body, every body with the same number of axes
+    pub fn new(bodies: &[Vec<i64>]) -> Result<System, String> {
+        let dimensions = bodies.first().map_or(0, |b| b.len());
+        if let Some(i) = bodies.iter().position(|b| b.len() != dimensions) {
+            return Err(format!(
+                "body {} has {} axes, expected {}",
+                i + 1,
+                bodies[i].len(),
+                dimensions
+            ));
+        }
+        let axes = (0..dimensions)
+            .map(|d| Axis::new(bodies.iter().map(|b| b[d]).collect()))
+            .collect();
+        Ok(System { axes })
+    }
+
+    pub fn bodies(&self) -> usize {
+        self.axes.first().map_or(0, |a| a.positions.len())
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.axes.len()
+    }
+
+    pub fn step(&mut self) {
+        for axis in self.axes.iter_mut() {
+            axis.step();
+        }
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for axis in self.axes.iter_mut() {
+            for _ in 0..steps {
+                axis.step();
+            }
+        }
+    }
+
+    pub fn position(&self, body: usize) -> Vec<i64> {
+        self.axes.iter().map(|a| a.positions[body]).collect()
+    }
+
+    pub fn velocity(&self, body: usize) -> Vec<i64> {
+        self.axes.iter().map(|a| a.velocities[body]).collect()
+    }
+
+    pub fn potential_energy(&self, body: usize) -> i64 {
+        self.axes.iter().map(|a| a.positions[body].abs()).sum()
+    }
+
+    pub fn kinetic_energy(&self, body: usize) -> i64 {
+        self.axes.iter().map(|a| a.velocities[body].abs()).sum()
+    }
+
+    pub fn total_energy(&self) -> i64 {
+        (0..self.bodies())
+            .map(|b| self.potential_energy(b) * self.kinetic_energy(b))
+            .sum()
+    }
+}
+
+// either lines like <x=-1, y=0, z=2> with any number of axes, or csv with
+// one body per line and an optional header. the labels of the first body
+// set the axis order, later bodies may list the same labels in any order
+pub fn parse_bodies(contents: &str) -> Result<Vec<Vec<i64>>, String> {
+    let mut bodies = Vec::new();
+    // none until the first <..> body, empty when its values are unlabelled
+    let mut labels: Option<Vec<String>> = None;
+    let mut first = true;
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        // a header has no numbers in it at all
+        let may_be_header = first && line.split(',').all(|v| v.trim().parse::<i64>().is_err());
+        first = false;
+        let body = if line.starts_with('<') && line.ends_with('>') {
+            parse_labelled(&line[1..line.len() - 1], &mut labels)
+        } else {
+            match line.split(',').map(|v| v.trim().parse()).collect() {
+                Ok(body) => Ok(body),
+                Err(_) if may_be_header => continue,
+                Err(e) => Err(format!("{}", e)),
+            }
+        };
+        match body {
+            Ok(body) => bodies.push(body),
+            Err(e) => return Err(format!("line {}: {} in '{}'", i + 1, e, line)),
+        }
+    }
+    Ok(bodies)
+}
+
+fn parse_labelled(values: &str, labels: &mut Option<Vec<String>>) -> Result<Vec<i64>, String> {
+    let mut axes: Vec<(Option<&str>, i64)> = Vec::new();
+    for axis in values.split(',') {
+        let mut parts = axis.splitn(2, '=');
+        let (label, value) = match (parts.next(), parts.next()) {
+            (Some(label), Some(value)) => (Some(label.trim()), value),
+            _ => (None, axis),
+        };
+        let value = value.trim().parse().map_err(|e| format!("{}", e))?;
+        axes.push((label, value));
+    }
+    let names: Vec<String> = if axes.iter().all(|(label, _)| label.is_none()) {
+        Vec::new()
+    } else {
+        axes.iter()
+            .map(|(label, _)| label.map(String::from).ok_or("unlabelled axis"))
+            .collect::<Result<_, _>>()?
+    };
+    let expected = labels.get_or_insert_with(|| names.clone());
+    if expected.is_empty() {
+        if !names.is_empty() {
+            return Err(String::from("expected unlabelled axes"));
+        }
+        return Ok(axes.into_iter().map(|(_, value)| value).collect());
+    }
+    if names.len() != expected.len() {
+        return Err(format!("expected the axes <{}>", expected.join(", ")));
+    }
+    let mut body: Vec<Option<i64>> = vec![None; expected.len()];
+    for (name, (_, value)) in names.iter().zip(axes) {
+        match expected.iter().position(|e| e == name) {
+            Some(axis) if body[axis].is_none() => body[axis] = Some(value),
+            Some(_) => return Err(format!("axis {} given twice", name)),
+            None => return Err(format!("unknown axis {}", name)),
+        }
+    }
+    Ok(body.into_iter().map(|value| value.unwrap()).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_bodies, System};
+
+    #[test]
+    fn parse_formats() {
+        let bodies = parse_bodies("<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n").unwrap();
+        assert_eq!(bodies, vec![vec![-1, 0, 2], vec![2, -10, -7]]);
+        let bodies = parse_bodies("x,y,z,w\n1,2,3,4\n-5, 6, 7, 8\n").unwrap();
+        assert_eq!(bodies, vec![vec![1, 2, 3, 4], vec![-5, 6, 7, 8]]);
+        assert_eq!(
+            parse_bodies("<a=3>\n<a=-3>\n").unwrap(),
+            vec![vec![3], vec![-3]]
+        );
+        assert_eq!(
+            parse_bodies("1,2\n3,x\n"),
+            Err(String::from(
+                "line 2: invalid digit found in string in '3,x'"
+            ))
+        );
+        assert_eq!(
+            parse_bodies("1,2,x\n3,4,5\n"),
+            Err(String::from(
+                "line 1: invalid digit found in string in '1,2,x'"
+            ))
+        );
+        assert_eq!(
+            parse_bodies("\n\nx,y\n1,2\n\n3,4\n").unwrap(),
+            vec![vec![1, 2], vec![3, 4]]
+        );
+        assert_eq!(
+            System::new(&[vec![1, 2], vec![3]]),
+            Err(String::from("body 2 has 1 axes, expected 2"))
+        );
+    }
+
+    #[test]
+    fn labelled_axes() {
+        assert_eq!(
+            parse_bodies("<x=1, y=2, z=3>\n<z=6, x=4, y=5>\n").unwrap(),
+            vec![vec![1, 2, 3], vec![4, 5, 6]]
+        );
+        assert_eq!(
+            parse_bodies("<1, 2>\n<3, 4>\n").unwrap(),
+            vec![vec![1, 2], vec![3, 4]]
+        );
+        assert_eq!(
+            parse_bodies("<x=1, y=2>\n<x=3, w=4>\n"),
+            Err(String::from("line 2: unknown axis w in '<x=3, w=4>'"))
+        );
+        assert_eq!(
+            parse_bodies("<x=1, y=2>\n<x=3, x=4>\n"),
+            Err(String::from("line 2: axis x given twice in '<x=3, x=4>'"))
+        );
+        assert_eq!(
+            parse_bodies("<x=1, y=2>\n<x=3>\n"),
+            Err(String::from("line 2: expected the axes <x, y> in '<x=3>'"))
+        );
+        assert_eq!(
+            parse_bodies("<1, 2>\n<x=3, y=4>\n"),
+            Err(String::from(
+                "line 2: expected unlabelled axes in '<x=3, y=4>'"
+            ))
+        );
+        assert_eq!(
+            parse_bodies("<x=1, y=2>\n<3, 4>\n"),
+            Err(String::from("line 2: expected the axes <x, y> in '<3, 4>'"))
+        );
+        assert_eq!(
+            parse_bodies("<x=1, 2>\n"),
+            Err(String::from("line 1: unlabelled axis in '<x=1, 2>'"))
+        );
+    }
+
+    #[test]
+    fn any_number_of_axes() {
+        // two bodies on a line pull together and swing back
+        let mut system = System::new(&[vec![0], vec![3]]).unwrap();
+        system.step();
+        assert_eq!(system.position(0), vec![1]);
+        assert_eq!(system.velocity(1), vec![-1]);
+        assert_eq!(system.axes[0].find_cycle(), 8);
+
+        let mut system =
+            System::new(&[vec![1, 0, 0, 5], vec![0, 2, 0, -5], vec![0, 0, 3, 0]]).unwrap();
+        assert_eq!(system.dimensions(), 4);
+        assert_eq!(system.bodies(), 3);
+        let start = system.clone();
+        let cycles: Vec<u64> = system.axes.iter().map(|a| a.find_cycle()).collect();
+        let steps = crate::cycles::lcm_of(&cycles).unwrap() as usize;
+        system.run(steps);
+        assert_eq!(system, start);
+    }
+}
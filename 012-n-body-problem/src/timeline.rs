@@ -0,0 +1,222 @@
+use crate::system::System;
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BodyState {
+    pub position: Vec<i64>,
+    pub velocity: Vec<i64>,
+    pub potential: i64,
+    pub kinetic: i64,
+    pub total: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Sample {
+    pub step: usize,
+    pub bodies: Vec<BodyState>,
+    pub total_energy: i64,
+}
+impl Sample {
+    pub fn of(system: &System, step: usize) -> Sample {
+        let bodies = (0..system.bodies())
+            .map(|b| {
+                let potential = system.potential_energy(b);
+                let kinetic = system.kinetic_energy(b);
+                BodyState {
+                    position: system.position(b),
+                    velocity: system.velocity(b),
+                    potential,
+                    kinetic,
+                    total: potential * kinetic,
+                }
+            })
+            .collect();
+        Sample {
+            step,
+            bodies,
+            total_energy: system.total_energy(),
+        }
+    }
+
+    // one row per body
+    pub fn csv_rows(&self) -> Vec<String> {
+        self.bodies
+            .iter()
+            .enumerate()
+            .map(|(i, body)| {
+                let mut row = vec![self.step.to_string(), i.to_string()];
+                row.extend(body.position.iter().map(|p| p.to_string()));
+                row.extend(body.velocity.iter().map(|v| v.to_string()));
+                row.push(body.potential.to_string());
+                row.push(body.kinetic.to_string());
+                row.push(body.total.to_string());
+                row.push(self.total_energy.to_string());
+                row.join(",")
+            })
+            .collect()
+    }
+}
+
+pub fn csv_header(dimensions: usize) -> String {
+    let mut header = vec![String::from("step"), String::from("body")];
+    header.extend((0..dimensions).map(|d| format!("p{}", d)));
+    header.extend((0..dimensions).map(|d| format!("v{}", d)));
+    for column in ["potential", "kinetic", "total", "system_total"] {
+        header.push(column.to_string());
+    }
+    header.join(",")
+}
+
+// the state at every stride-th step from `from` up to and including `to`,
+// simulated lazily so long ranges don't have to fit in memory
+pub struct Timeline {
+    system: System,
+    // the step of the next sample, none once `to` has been passed
+    step: Option<usize>,
+    // how many steps to run before the next sample can be taken
+    behind: usize,
+    to: usize,
+    stride: usize,
+}
+impl Timeline {
+    pub fn new(system: &System, from: usize, to: usize, stride: usize) -> Timeline {
+        Timeline {
+            system: system.clone(),
+            step: Some(from).filter(|&from| from <= to),
+            behind: from,
+            to,
+            stride: stride.max(1),
+        }
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.system.dimensions()
+    }
+}
+impl Iterator for Timeline {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Sample> {
+        let step = self.step?;
+        self.system.run(self.behind);
+        let sample = Sample::of(&self.system, step);
+        // only simulate towards the next sample once it is asked for, and
+        // never past `to`
+        self.step = step
+            .checked_add(self.stride)
+            .filter(|&next| next <= self.to);
+        self.behind = self.stride;
+        Some(sample)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Expected csv or json, found {}", name)),
+        }
+    }
+}
+
+// writes the samples one at a time as they are simulated
+pub fn write_timeline<W: Write>(out: &mut W, timeline: Timeline, format: Format) -> io::Result<()> {
+    match format {
+        Format::Csv => {
+            writeln!(out, "{}", csv_header(timeline.dimensions()))?;
+            for sample in timeline {
+                for row in sample.csv_rows() {
+                    writeln!(out, "{}", row)?;
+                }
+            }
+        }
+        Format::Json => {
+            writeln!(out, "[")?;
+            for (i, sample) in timeline.enumerate() {
+                let separator = if i == 0 { "" } else { "," };
+                writeln!(out, "{}{}", separator, serde_json::to_string(&sample)?)?;
+            }
+            writeln!(out, "]")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_timeline, Format, Timeline};
+    use crate::system::System;
+
+    fn sample() -> System {
+        System::new(&[
+            vec![-1, 0, 2],
+            vec![2, -10, -7],
+            vec![4, -8, 8],
+            vec![3, 5, -1],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn strided_range() {
+        let samples: Vec<_> = Timeline::new(&sample(), 2, 10, 4).collect();
+        let steps: Vec<usize> = samples.iter().map(|s| s.step).collect();
+        assert_eq!(steps, vec![2, 6, 10]);
+        let last = &samples[2];
+        assert_eq!(last.bodies[0].position, vec![2, 1, -3]);
+        assert_eq!(last.bodies[0].velocity, vec![-3, -2, 1]);
+        assert_eq!(
+            (
+                last.bodies[0].potential,
+                last.bodies[0].kinetic,
+                last.bodies[0].total
+            ),
+            (6, 6, 36)
+        );
+        assert_eq!(last.total_energy, 179);
+        assert_eq!(Timeline::new(&sample(), 5, 4, 1).count(), 0);
+    }
+
+    #[test]
+    fn stops_at_the_last_sample() {
+        let samples: Vec<_> = Timeline::new(&sample(), 5, 5, 3000000000).collect();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].step, 5);
+        let steps: Vec<usize> = Timeline::new(&sample(), 7, 7, usize::MAX)
+            .map(|s| s.step)
+            .collect();
+        assert_eq!(steps, vec![7]);
+    }
+
+    fn written(format: Format) -> String {
+        let mut out = Vec::new();
+        write_timeline(&mut out, Timeline::new(&sample(), 0, 1, 1), format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_and_json() {
+        let csv = written(Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 2 * 4);
+        assert_eq!(
+            lines[0],
+            "step,body,p0,p1,p2,v0,v1,v2,potential,kinetic,total,system_total"
+        );
+        assert_eq!(lines[1], "0,0,-1,0,2,0,0,0,3,0,0,0");
+        assert_eq!(lines[5], "1,0,2,-1,1,3,-1,-1,4,5,20,229");
+
+        let json: serde_json::Value = serde_json::from_str(&written(Format::Json)).unwrap();
+        assert_eq!(json[1]["step"], 1);
+        assert_eq!(json[1]["bodies"][0]["velocity"][0], 3);
+        assert_eq!(json[1]["total_energy"], 229);
+        assert!(Format::parse("xml").is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use crate::replay::{Frame, Recording};
+use crate::{compute, Program};
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+impl Tile {
+    pub fn from_id(id: i128) -> Option<Tile> {
+        match id {
+            0 => Some(Tile::Empty),
+            1 => Some(Tile::Wall),
+            2 => Some(Tile::Block),
+            3 => Some(Tile::Paddle),
+            4 => Some(Tile::Ball),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => '=',
+            Tile::Paddle => '-',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Screen {
+    pub tiles: HashMap<(i128, i128), Tile>,
+    pub score: i128,
+}
+impl Screen {
+    // (x, y, tile) triples, with (-1, 0, score) updating the score display
+    pub fn apply(&mut self, output: &[i128]) -> Result<(), String> {
+        if !output.len().is_multiple_of(3) {
+            return Err(format!(
+                "{} values don't make whole (x, y, tile) triples",
+                output.len()
+            ));
+        }
+        for triple in output.chunks(3) {
+            let (x, y, id) = (triple[0], triple[1], triple[2]);
+            if (x, y) == (-1, 0) {
+                self.score = id;
+                continue;
+            }
+            let tile =
+                Tile::from_id(id).ok_or_else(|| format!("Unknown tile {} at {},{}", id, x, y))?;
+            self.tiles.insert((x, y), tile);
+        }
+        Ok(())
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.values().filter(|&&t| t == tile).count()
+    }
+
+    pub fn find(&self, tile: Tile) -> Option<(i128, i128)> {
+        self.tiles
+            .iter()
+            .find(|(_, &t)| t == tile)
+            .map(|(&position, _)| position)
+    }
+
+    pub fn render(&self) -> String {
+        let max_x = self.tiles.keys().map(|p| p.0).max().unwrap_or(0);
+        let max_y = self.tiles.keys().map(|p| p.1).max().unwrap_or(0);
+        let mut sb = format!("score: {}\n", self.score);
+        for y in 0..=max_y {
+            for x in 0..=max_x {
+                let tile = self.tiles.get(&(x, y)).copied().unwrap_or(Tile::Empty);
+                sb.push(tile.to_char());
+            }
+            sb.push('\n');
+        }
+        sb
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Joystick {
+    Left,
+    Neutral,
+    Right,
+}
+impl Joystick {
+    pub fn from_value(value: i128) -> Option<Joystick> {
+        match value {
+            -1 => Some(Joystick::Left),
+            0 => Some(Joystick::Neutral),
+            1 => Some(Joystick::Right),
+            _ => None,
+        }
+    }
+
+    pub fn value(self) -> i128 {
+        match self {
+            Joystick::Left => -1,
+            Joystick::Neutral => 0,
+            Joystick::Right => 1,
+        }
+    }
+
+    // the way to move the paddle from x to target
+    pub fn towards(x: i128, target: i128) -> Joystick {
+        if target < x {
+            Joystick::Left
+        } else if target > x {
+            Joystick::Right
+        } else {
+            Joystick::Neutral
+        }
+    }
+}
+
+pub trait Controller {
+    fn decide(&mut self, screen: &Screen) -> Joystick;
+}
+
+// keeps the paddle under the ball
+pub struct BallTracking;
+impl Controller for BallTracking {
+    fn decide(&mut self, screen: &Screen) -> Joystick {
+        match (screen.find(Tile::Paddle), screen.find(Tile::Ball)) {
+            (Some(paddle), Some(ball)) => Joystick::towards(paddle.0, ball.0),
+            _ => Joystick::Neutral,
+        }
+    }
+}
+
+// works out where a falling ball reaches the paddle's row, bouncing off the
+// side walls, and waits there; blocks in the way are only noticed once the
+// ball bounces off them
+#[derive(Default)]
+pub struct Predictive {
+    previous: Option<(i128, i128)>,
+}
+impl Predictive {
+    pub fn new() -> Predictive {
+        Predictive::default()
+    }
+}
+impl Controller for Predictive {
+    fn decide(&mut self, screen: &Screen) -> Joystick {
+        let (paddle, ball) = match (screen.find(Tile::Paddle), screen.find(Tile::Ball)) {
+            (Some(paddle), Some(ball)) => (paddle, ball),
+            _ => return Joystick::Neutral,
+        };
+        let previous = self.previous.replace(ball);
+        let (dx, dy) = match previous {
+            Some(p) => (ball.0 - p.0, ball.1 - p.1),
+            None => return Joystick::towards(paddle.0, ball.0),
+        };
+        if dy <= 0 || dx == 0 {
+            return Joystick::towards(paddle.0, ball.0);
+        }
+        let walls: Vec<i128> = screen
+            .tiles
+            .iter()
+            .filter(|(&(_, y), &t)| t == Tile::Wall && y == ball.1)
+            .map(|(&(x, _), _)| x)
+            .collect();
+        let left = walls
+            .iter()
+            .copied()
+            .filter(|&x| x < ball.0)
+            .max()
+            .unwrap_or(i128::MIN);
+        let right = walls
+            .iter()
+            .copied()
+            .filter(|&x| x > ball.0)
+            .min()
+            .unwrap_or(i128::MAX);
+        let (mut x, mut dx) = (ball.0, dx);
+        for _ in ball.1..paddle.1 - 1 {
+            if x + dx <= left || x + dx >= right {
+                dx = -dx;
+            }
+            x += dx;
+        }
+        Joystick::towards(paddle.0, x)
+    }
+}
+
+pub enum Display {
+    Headless,
+    // redraws the terminal after every frame and waits the delay
+    Terminal(Duration),
+}
+
+// the game's intcode program, stopped whenever it waits for the joystick
+pub struct Arcade {
+    memory: HashMap<i128, i128>,
+    inputs: Vec<i128>,
+    op_pos: i128,
+    rel_pos: i128,
+    input_pos: usize,
+    halted: bool,
+    pub screen: Screen,
+    // joystick inputs so far
+    pub frames: usize,
+    pub recording: Option<Recording>,
+}
+impl Arcade {
+    // runs up to the first joystick input, quarters set the coins inserted
+    pub fn new(program: &Program, quarters: Option<i128>) -> Result<Arcade, String> {
+        Arcade::load(program, quarters, false)
+    }
+
+    // keeps every joystick input and screen update, starting from the boot
+    pub fn recorded(program: &Program, quarters: Option<i128>) -> Result<Arcade, String> {
+        Arcade::load(program, quarters, true)
+    }
+
+    fn load(program: &Program, quarters: Option<i128>, record: bool) -> Result<Arcade, String> {
+        let mut memory = program.memory.clone();
+        if let Some(quarters) = quarters {
+            memory.insert(0, quarters);
+        }
+        let mut arcade = Arcade {
+            memory,
+            inputs: Vec::new(),
+            op_pos: 0,
+            rel_pos: 0,
+            input_pos: 0,
+            halted: false,
+            screen: Screen::default(),
+            frames: 0,
+            recording: if record {
+                Some(Recording {
+                    quarters,
+                    frames: Vec::new(),
+                })
+            } else {
+                None
+            },
+        };
+        arcade.resume(None)?;
+        Ok(arcade)
+    }
+
+    fn resume(&mut self, joystick: Option<Joystick>) -> Result<(), String> {
+        let (output, op_pos, rel_pos, input_pos, op_code) = compute(
+            &mut self.memory,
+            &self.inputs,
+            self.op_pos,
+            self.rel_pos,
+            self.input_pos,
+        );
+        self.op_pos = op_pos;
+        self.rel_pos = rel_pos;
+        self.input_pos = input_pos;
+        self.halted = op_code == 99;
+        self.screen.apply(&output)?;
+        if let Some(recording) = self.recording.as_mut() {
+            recording.frames.push(Frame {
+                joystick,
+                output,
+                score: self.screen.score,
+                blocks: self.screen.count(Tile::Block),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn step(&mut self, joystick: Joystick) -> Result<(), String> {
+        if self.halted {
+            return Err(String::from("The game is over"));
+        }
+        self.inputs.push(joystick.value());
+        self.frames += 1;
+        self.resume(Some(joystick))
+    }
+
+    // plays until the program halts and returns the final score
+    pub fn play(
+        &mut self,
+        controller: &mut dyn Controller,
+        display: &Display,
+    ) -> Result<i128, String> {
+        self.show(display);
+        while !self.halted {
+            let joystick = controller.decide(&self.screen);
+            self.step(joystick)?;
+            self.show(display);
+        }
+        Ok(self.screen.score)
+    }
+
+    pub fn show(&self, display: &Display) {
+        if let Display::Terminal(delay) = display {
+            // clear the terminal and move the cursor back to the top
+            print!("\x1b[2J\x1b[H{}", self.screen.render());
+            println!(
+                "frame {}, blocks left {}",
+                self.frames,
+                self.screen.count(Tile::Block)
+            );
+            thread::sleep(*delay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Arcade, BallTracking, Display, Joystick, Predictive, Screen, Tile};
+    use crate::parse_input;
+
+    #[test]
+    fn screen_updates() {
+        let mut screen = Screen::default();
+        screen.apply(&[1, 2, 3, 6, 5, 4, -1, 0, 12345]).unwrap();
+        assert_eq!(screen.find(Tile::Paddle), Some((1, 2)));
+        assert_eq!(screen.find(Tile::Ball), Some((6, 5)));
+        assert_eq!(screen.score, 12345);
+        screen.apply(&[6, 5, 0]).unwrap();
+        assert_eq!(screen.find(Tile::Ball), None);
+        assert_eq!(screen.count(Tile::Empty), 1);
+        assert!(screen.apply(&[1, 2]).is_err());
+        assert_eq!(
+            screen.apply(&[1, 2, 7]),
+            Err(String::from("Unknown tile 7 at 1,2"))
+        );
+        assert_eq!(Joystick::towards(4, 2), Joystick::Left);
+    }
+
+    #[test]
+    fn controllers_clear_the_board() {
+        let program = parse_input(include_str!("../input.txt"));
+        let mut tracking = Arcade::new(&program, Some(2)).unwrap();
+        let score = tracking
+            .play(&mut BallTracking, &Display::Headless)
+            .unwrap();
+        assert_eq!(score, 12263);
+        assert_eq!(tracking.screen.count(Tile::Block), 0);
+
+        let mut predictive = Arcade::new(&program, Some(2)).unwrap();
+        let score = predictive
+            .play(&mut Predictive::new(), &Display::Headless)
+            .unwrap();
+        assert_eq!(score, 12263);
+        assert_eq!(predictive.screen.count(Tile::Block), 0);
+        assert!(predictive.step(Joystick::Neutral).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+pub mod arcade;
+pub mod replay;
+
+use arcade::{Arcade, BallTracking, Display, Tile};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct Program {
+    pub memory: HashMap<i128, i128>,
+}
+
+pub fn parse_input(contents: &str) -> Program {
+    let mut memory_map: HashMap<i128, i128> = HashMap::new();
+    for (i, v) in contents.trim().split_terminator(',').enumerate() {
+        let val = v.parse::<i128>().unwrap();
+        memory_map.insert(i as i128, val);
+    }
+    Program { memory: memory_map }
+}
+
+// blocks on the screen once the game without quarters has drawn it
+pub fn part1(program: &Program) -> usize {
+    let arcade = Arcade::new(program, None).unwrap_or_else(|e| panic!("{}", e));
+    arcade.screen.count(Tile::Block)
+}
+
+pub fn part2(program: &Program) -> i128 {
+    let mut arcade = Arcade::new(program, Some(2)).unwrap_or_else(|e| panic!("{}", e));
+    arcade
+        .play(&mut BallTracking, &Display::Headless)
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn compute(
+    memory: &mut HashMap<i128, i128>,
+    input: &[i128],
+    op_position: i128,
+    rel_position: i128,
+    input_position: usize,
+) -> (Vec<i128>, i128, i128, usize, i128) {
+    let mut output = Vec::new();
+    let mut op_pos = op_position;
+    let mut rel_base = rel_position;
+    let mut input_pos = input_position;
+    let mut operation_code;
+    loop {
+        let (op_code, param_modes) = extract_op_code_and_param_modes(memory, op_pos);
+        operation_code = op_code;
+
+        let move_by;
+        match op_code {
+            99 => break,
+            1 => {
+                let write_address =
+                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                memory.insert(write_address, args[0] + args[1]);
+                move_by = 4;
+            }
+            2 => {
+                let write_address =
+                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                memory.insert(write_address, args[0] * args[1]);
+                move_by = 4;
+            }
+            3 => {
+                let write_address =
+                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[0]);
+                if input_pos == input.len() {
+                    return (output, op_pos, rel_base, input_pos, operation_code);
+                }
+                memory.insert(write_address, input[input_pos]);
+                input_pos += 1;
+                move_by = 2;
+            }
+            4 => {
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                output.push(args[0]);
+                move_by = 2;
+            }
+            5 => {
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                if args[0] > 0 {
+                    op_pos = args[1];
+                    continue;
+                }
+                move_by = 3;
+            }
+            6 => {
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                if args[0] == 0 {
+                    op_pos = args[1];
+                    continue;
+                }
+                move_by = 3;
+            }
+            7 => {
+                let write_address =
+                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                if args[0] < args[1] {
+                    memory.insert(write_address, 1);
+                } else {
+                    memory.insert(write_address, 0);
+                }
+                move_by = 4;
+            }
+            8 => {
+                let write_address =
+                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                if args[0] == args[1] {
+                    memory.insert(write_address, 1);
+                } else {
+                    memory.insert(write_address, 0);
+                }
+                move_by = 4;
+            }
+            9 => {
+                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
+                rel_base += args[0];
+                move_by = 2;
+            }
+            _ => panic!("Something went wrong: {}", op_code),
+        }
+        op_pos += move_by;
+    }
+    (output, -1, -1, usize::MAX, operation_code)
+}
+
+fn get_value(memory: &mut HashMap<i128, i128>, key: i128) -> i128 {
+    if let Some(value) = memory.get(&key) {
+        *value
+    } else {
+        let value = 0;
+        memory.insert(key, value);
+        value
+    }
+}
+
+fn get_argument_values(
+    memory: &mut HashMap<i128, i128>,
+    op_position: i128,
+    rel_position: i128,
+    param_modes: Vec<i128>,
+) -> Vec<i128> {
+    let mut args = Vec::new();
+    for (i, &mode) in param_modes.iter().enumerate() {
+        match mode {
+            0 => {
+                let pos = get_value(memory, op_position + (i as i128) + 1);
+                args.push(get_value(memory, pos));
+            }
+            1 => {
+                args.push(get_value(memory, op_position + (i as i128) + 1));
+            }
+            2 => {
+                let pos = rel_position + get_value(memory, op_position + (i as i128) + 1);
+                args.push(get_value(memory, pos));
+            }
+            _ => panic!("Unexpected param mode"),
+        }
+    }
+    args
+}
+
+fn get_write_address(
+    memory: &mut HashMap<i128, i128>,
+    op_code: i128,
+    op_position: i128,
+    rel_position: i128,
+    param_mode: i128,
+) -> i128 {
+    let mut offset = 3;
+    if op_code == 3 {
+        offset = 1;
+    }
+    match param_mode {
+        0 => get_value(memory, op_position + offset),
+        2 => rel_position + get_value(memory, op_position + offset),
+        _ => panic!("Unexpected param mode"),
+    }
+}
+
+fn extract_op_code_and_param_modes(
+    memory: &mut HashMap<i128, i128>,
+    pos: i128,
+) -> (i128, Vec<i128>) {
+    let val = get_value(memory, pos);
+    let op_code = val % 100;
+    let mut modes = Vec::new();
+    let mut modes_digits = val / 100;
+    let param_num = match op_code {
+        1 | 2 | 7 | 8 => 3,
+        5 | 6 => 2,
+        3 | 4 | 9 => 1,
+        99 => 0,
+        _ => panic!("Invalid op code {}", op_code),
+    };
+    for _ in 0..param_num {
+        modes.push(modes_digits % 10);
+        modes_digits /= 10;
+    }
+    (op_code, modes)
+}
@@ -1,273 +1,111 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use aoc_bench::{input_source, read_input, take_option, Bench};
+use care_package::arcade::{Arcade, BallTracking, Controller, Display, Predictive, Tile};
+use care_package::replay::{replay, Recording};
+use care_package::{parse_input, part1, part2};
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
 
 fn main() {
-    let mut file = File::open("input.txt").expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Failed to read input file");
-    let mut i = 0;
-    let mut memory_map: HashMap<i128, i128> = HashMap::new();
-    for v in contents.split_terminator(',') {
-        let val = v.parse::<i128>().unwrap();
-        memory_map.insert(i, val);
-        i += 1;
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "bench" {
+        let mut bench = Bench::from_args(&args[2..]);
+        let contents = read_input(&bench.input);
+        let input = parse_input(&contents);
+        bench.time("parse", || parse_input(&contents));
+        bench.time("part1", || part1(&input));
+        bench.time("part2", || part2(&input));
+        bench.finish();
+        return;
     }
 
-    let mut visited_map: HashMap<(i128, i128), i128> = HashMap::new();
-
-    let painted_panels_number = find_block_tiles_count(&mut memory_map.clone(), &mut visited_map);
-
-    println!("Care Package part1 Solution: {:?}", painted_panels_number);
-
-    visited_map = HashMap::new();
-    let mut free_game_memory_map = memory_map.clone();
-    free_game_memory_map.insert(0, 2);
-
-    println!("Care Package part2 Solution:");
-    run_game(&mut free_game_memory_map, &mut visited_map);
-}
-
-fn find_block_tiles_count(
-    memory: &mut HashMap<i128, i128>,
-    visited: &mut HashMap<(i128, i128), i128>,
-) -> i128 {
-    run_game(memory, visited);
-    let mut count = 0;
-    for value in visited.values() {
-        if *value == 2 {
-            count += 1;
-        }
-    }
-
-    count
-}
-
-fn run_game(memory: &mut HashMap<i128, i128>, visited: &mut HashMap<(i128, i128), i128>) {
-    let mut inputs = Vec::new();
-    let (mut output, mut op_pos, mut rel_pos, mut input_pos, mut op_code) =
-        compute(memory, &inputs, 0, 0, 0);
-    let mut curr_ball_pos_x = 0;
-    let mut curr_padle_pos_x = 0;
-    while op_code != 99 {
-        let mut i = 0;
-        while i < output.len() {
-            let new_x = output[i];
-            let new_y = output[i + 1];
-            let new_id = output[i + 2];
-            if !(new_x == -1 && new_y == 0) {
-                visited.insert((new_x, new_y), new_id);
-                if new_id == 4 {
-                    curr_ball_pos_x = new_x;
-                } else if new_id == 3 {
-                    curr_padle_pos_x = new_x;
-                }
-            }
-            i += 3;
-        }
-        if curr_ball_pos_x < curr_padle_pos_x {
-            inputs.push(-1);
-        } else if curr_ball_pos_x > curr_padle_pos_x {
-            inputs.push(1);
+    // play [input] [--controller <track|predict>] [--render] [--delay <ms>]
+    //      [--record <file>]
+    // plays the game headless, or drawn in the terminal after every frame
+    if args.len() > 1 && args[1] == "play" {
+        let mut rest: Vec<String> = args[2..].to_vec();
+        let controller = take_option(&mut rest, "--controller", 1)
+            .map_or(String::from("track"), |c| c[0].clone());
+        let display = display_options(&mut rest);
+        let record = take_option(&mut rest, "--record", 1);
+        let source = input_source(rest.first().cloned());
+        let program = parse_input(&read_input(&source));
+        let mut controller: Box<dyn Controller> = match controller.as_str() {
+            "track" => Box::new(BallTracking),
+            "predict" => Box::new(Predictive::new()),
+            _ => panic!("Expected track or predict"),
+        };
+        let mut arcade = if record.is_some() {
+            Arcade::recorded(&program, Some(2))
         } else {
-            inputs.push(0);
+            Arcade::new(&program, Some(2))
         }
-        let (new_output, new_op_pos, new_rel_pos, new_input_pos, new_op_code) =
-            compute(memory, &inputs, op_pos, rel_pos, input_pos);
-        output = new_output;
-        op_pos = new_op_pos;
-        rel_pos = new_rel_pos;
-        input_pos = new_input_pos;
-        op_code = new_op_code;
-    }
-
-    // find the latest output score
-    let mut i = 0;
-    while i < output.len() {
-        let new_x = output[i];
-        let new_y = output[i + 1];
-        let new_id = output[i + 2];
-        if new_x == -1 && new_y == 0 {
-            println!("The final score is {}", new_id);
+        .unwrap_or_else(|e| panic!("{}", e));
+        let score = arcade
+            .play(controller.as_mut(), &display)
+            .unwrap_or_else(|e| panic!("{}", e));
+        println!(
+            "score {} after {} frames, {} blocks left",
+            score,
+            arcade.frames,
+            arcade.screen.count(Tile::Block)
+        );
+        if let (Some(file), Some(recording)) = (record, arcade.recording) {
+            fs::write(&file[0], recording.to_text()).expect("Failed to write the replay");
         }
-        i += 3;
+        return;
     }
-}
 
-fn compute(
-    memory: &mut HashMap<i128, i128>,
-    input: &Vec<i128>,
-    op_position: i128,
-    rel_position: i128,
-    input_position: usize,
-) -> (Vec<i128>, i128, i128, usize, i128) {
-    let mut output = Vec::new();
-    let mut op_pos = op_position;
-    let mut rel_base = rel_position;
-    let mut input_pos = input_position;
-    let mut operation_code;
-    loop {
-        let (op_code, param_modes) = extract_op_code_and_param_modes(memory, op_pos);
-        operation_code = op_code;
-
-        let move_by;
-        match op_code {
-            99 => break,
-            1 => {
-                let write_address =
-                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                memory.insert(write_address, args[0] + args[1]);
-                move_by = 4;
-            }
-            2 => {
-                let write_address =
-                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                memory.insert(write_address, args[0] * args[1]);
-                move_by = 4;
-            }
-            3 => {
-                let write_address =
-                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[0]);
-                if input_pos == input.len() {
-                    return (output, op_pos, rel_base, input_pos, operation_code);
-                }
-                memory.insert(write_address, input[input_pos]);
-                input_pos = input_pos + 1;
-                move_by = 2;
-            }
-            4 => {
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                output.push(args[0]);
-                move_by = 2;
-            }
-            5 => {
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                if args[0] > 0 {
-                    op_pos = args[1];
-                    continue;
-                }
-                move_by = 3;
-            }
-            6 => {
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                if args[0] == 0 {
-                    op_pos = args[1];
-                    continue;
-                }
-                move_by = 3;
-            }
-            7 => {
-                let write_address =
-                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                if args[0] < args[1] {
-                    memory.insert(write_address, 1);
-                } else {
-                    memory.insert(write_address, 0);
-                }
-                move_by = 4;
-            }
-            8 => {
-                let write_address =
-                    get_write_address(memory, op_code, op_pos, rel_base, param_modes[2]);
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                if args[0] == args[1] {
-                    memory.insert(write_address, 1);
-                } else {
-                    memory.insert(write_address, 0);
-                }
-                move_by = 4;
-            }
-            9 => {
-                let args = get_argument_values(memory, op_pos, rel_base, param_modes);
-                rel_base += args[0];
-                move_by = 2;
+    // replay <file> [input] [--render] [--delay <ms>] plays a recorded game
+    // again and checks every frame matches, timeline <file> [--every <n>]
+    // prints its score and blocks left over time
+    if args.len() > 1 && (args[1] == "replay" || args[1] == "timeline") {
+        if args.len() < 3 {
+            eprintln!("usage: {} <file> [options]", args[1]);
+            process::exit(1);
+        }
+        let mut rest: Vec<String> = args[3..].to_vec();
+        let display = display_options(&mut rest);
+        let every = take_option(&mut rest, "--every", 1)
+            .map_or(100, |e| e[0].parse().expect("Invalid --every"));
+        let recording = Recording::parse(&read_input(&args[2])).unwrap_or_else(|e| panic!("{}", e));
+        if args[1] == "timeline" {
+            println!("{}", recording.report(every));
+            return;
+        }
+        let source = input_source(rest.first().cloned());
+        let program = parse_input(&read_input(&source));
+        match replay(&program, &recording, &display) {
+            Ok(arcade) => println!(
+                "replayed {} frames, score {}",
+                arcade.frames, arcade.screen.score
+            ),
+            Err(e) => {
+                eprintln!("replay failed: {}", e);
+                process::exit(1);
             }
-            _ => panic!("Something went wrong: {}", op_code),
         }
-        op_pos = op_pos + move_by;
+        return;
     }
-    (output, -1, -1, usize::max_value(), operation_code)
-}
 
-fn get_value(memory: &mut HashMap<i128, i128>, key: i128) -> i128 {
-    if let Some(value) = memory.get(&key) {
-        return *value;
-    } else {
-        let value = 0;
-        memory.insert(key, value);
-        return value;
-    }
-}
+    let source = input_source(env::args().nth(1));
+    let contents = read_input(&source);
+    let program = parse_input(&contents);
 
-fn get_argument_values(
-    memory: &mut HashMap<i128, i128>,
-    op_position: i128,
-    rel_position: i128,
-    param_modes: Vec<i128>,
-) -> Vec<i128> {
-    let mut args = Vec::new();
-    for i in 0..param_modes.len() {
-        match param_modes[i] {
-            0 => {
-                let pos = get_value(memory, op_position + (i as i128) + 1);
-                args.push(get_value(memory, pos));
-            }
-            1 => {
-                args.push(get_value(memory, op_position + (i as i128) + 1));
-            }
-            2 => {
-                let pos = rel_position + get_value(memory, op_position + (i as i128) + 1);
-                args.push(get_value(memory, pos));
-            }
-            _ => panic!("Unexpected param mode"),
-        }
-    }
-    args
-}
+    println!("Care Package part1 Solution: {:?}", part1(&program));
 
-fn get_write_address(
-    memory: &mut HashMap<i128, i128>,
-    op_code: i128,
-    op_position: i128,
-    rel_position: i128,
-    param_mode: i128,
-) -> i128 {
-    let addr;
-    let mut offset = 3;
-    if op_code == 3 {
-        offset = 1;
-    }
-    match param_mode {
-        0 => addr = get_value(memory, op_position + offset),
-        2 => addr = rel_position + get_value(memory, op_position + offset),
-        _ => panic!("Unexpected param mode"),
-    }
-    addr
+    println!("Care Package part2 Solution: {}", part2(&program));
 }
 
-fn extract_op_code_and_param_modes(
-    memory: &mut HashMap<i128, i128>,
-    pos: i128,
-) -> (i128, Vec<i128>) {
-    let val = get_value(memory, pos);
-    let op_code = val % 100;
-    let mut modes = Vec::new();
-    let mut modes_digits = val / 100;
-    let param_num;
-    match op_code {
-        1 | 2 | 7 | 8 => param_num = 3,
-        5 | 6 => param_num = 2,
-        3 | 4 | 9 => param_num = 1,
-        99 => param_num = 0,
-        _ => panic!("Invalid op code {}", op_code),
-    }
-    for _ in 0..param_num {
-        modes.push(modes_digits % 10);
-        modes_digits /= 10;
+// --render and --delay <ms>
+fn display_options(args: &mut Vec<String>) -> Display {
+    let render = take_option(args, "--render", 0).is_some();
+    let delay =
+        take_option(args, "--delay", 1).map_or(20, |d| d[0].parse().expect("Invalid --delay"));
+    if render {
+        Display::Terminal(Duration::from_millis(delay))
+    } else {
+        Display::Headless
     }
-    (op_code, modes)
 }
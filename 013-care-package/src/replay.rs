@@ -0,0 +1,244 @@
+use crate::arcade::{Arcade, Display, Joystick};
+use crate::Program;
+
+// what the program drew after one joystick input, or after booting
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub joystick: Option<Joystick>,
+    pub output: Vec<i128>,
+    pub score: i128,
+    pub blocks: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    pub quarters: Option<i128>,
+    pub frames: Vec<Frame>,
+}
+impl Recording {
+    // a "quarters" line, then one line per frame with the joystick input
+    // ("boot" for the first one), score, blocks left and the raw output
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![match self.quarters {
+            Some(quarters) => format!("quarters {}", quarters),
+            None => String::from("quarters -"),
+        }];
+        for frame in self.frames.iter() {
+            let joystick = frame
+                .joystick
+                .map_or(String::from("boot"), |j| j.value().to_string());
+            let output: Vec<String> = frame
+                .output
+                .chunks(3)
+                .map(|t| {
+                    t.iter()
+                        .map(|v| v.to_string())
+                        .collect::<Vec<String>>()
+                        .join(",")
+                })
+                .collect();
+            lines.push(format!(
+                "{} {} {} {}",
+                joystick,
+                frame.score,
+                frame.blocks,
+                output.join(" ")
+            ));
+        }
+        lines.join("\n")
+    }
+
+    pub fn parse(contents: &str) -> Result<Recording, String> {
+        // line numbers count the blank lines too
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (header_line, header) = match lines.next() {
+            Some((i, line)) => (i, line.split_whitespace().collect::<Vec<_>>()),
+            None => return Err(String::from("line 1: expected quarters <n|->")),
+        };
+        let quarters = if header.len() == 2 && header[0] == "quarters" {
+            if header[1] == "-" {
+                None
+            } else {
+                Some(
+                    header[1]
+                        .parse()
+                        .map_err(|e| format!("line {}: {}", header_line, e))?,
+                )
+            }
+        } else {
+            return Err(format!("line {}: expected quarters <n|->", header_line));
+        };
+        let mut frames = Vec::new();
+        for (line_number, line) in lines {
+            let error = |e: String| format!("line {}: {}", line_number, e);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 {
+                return Err(error(String::from(
+                    "expected joystick, score and blocks left",
+                )));
+            }
+            let joystick = match fields[0] {
+                "boot" => None,
+                value => Some(
+                    value
+                        .parse()
+                        .ok()
+                        .and_then(Joystick::from_value)
+                        .ok_or_else(|| error(format!("invalid joystick input {}", value)))?,
+                ),
+            };
+            let score = fields[1].parse().map_err(|e| error(format!("{}", e)))?;
+            let blocks = fields[2].parse().map_err(|e| error(format!("{}", e)))?;
+            let mut output = Vec::new();
+            for triple in fields[3..].iter() {
+                for v in triple.split(',') {
+                    output.push(v.parse().map_err(|e| error(format!("{}", e)))?);
+                }
+            }
+            frames.push(Frame {
+                joystick,
+                output,
+                score,
+                blocks,
+            });
+        }
+        Ok(Recording { quarters, frames })
+    }
+
+    pub fn inputs(&self) -> Vec<Joystick> {
+        self.frames.iter().filter_map(|f| f.joystick).collect()
+    }
+
+    // the first frame without blocks left
+    pub fn cleared_at(&self) -> Option<usize> {
+        self.frames.iter().position(|f| f.blocks == 0)
+    }
+
+    // score and blocks left every `every` frames, and after the last one
+    pub fn report(&self, every: usize) -> String {
+        let every = every.max(1);
+        let mut lines = vec![format!("{:>8}{:>10}{:>8}", "frame", "score", "blocks")];
+        for (i, frame) in self.frames.iter().enumerate() {
+            if i % every == 0 || i + 1 == self.frames.len() {
+                lines.push(format!("{:>8}{:>10}{:>8}", i, frame.score, frame.blocks));
+            }
+        }
+        match self.cleared_at() {
+            Some(i) => lines.push(format!("board cleared after {} frames", i)),
+            None => lines.push(String::from("board not cleared")),
+        }
+        lines.join("\n")
+    }
+}
+
+// plays the recorded inputs again, checking the program draws the same
+// frames it drew when they were recorded
+pub fn replay(
+    program: &Program,
+    recording: &Recording,
+    display: &Display,
+) -> Result<Arcade, String> {
+    let mut arcade = Arcade::recorded(program, recording.quarters)?;
+    arcade.show(display);
+    for joystick in recording.inputs() {
+        if arcade.halted() {
+            return Err(format!(
+                "The game ended after {} of {} inputs",
+                arcade.frames,
+                recording.inputs().len()
+            ));
+        }
+        arcade.step(joystick)?;
+        arcade.show(display);
+    }
+    let frames = &arcade.recording.as_ref().unwrap().frames;
+    if let Some(i) = (0..frames.len()).find(|&i| recording.frames.get(i) != Some(&frames[i])) {
+        return Err(format!("Frame {} doesn't match the recording", i));
+    }
+    if frames.len() != recording.frames.len() {
+        return Err(format!(
+            "The recording has {} frames, the replay {}",
+            recording.frames.len(),
+            frames.len()
+        ));
+    }
+    Ok(arcade)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{replay, Recording};
+    use crate::arcade::{Arcade, BallTracking, Display, Joystick, Predictive};
+    use crate::parse_input;
+
+    #[test]
+    fn record_and_replay() {
+        let program = parse_input(include_str!("../input.txt"));
+        let mut arcade = Arcade::recorded(&program, Some(2)).unwrap();
+        arcade
+            .play(&mut Predictive::new(), &Display::Headless)
+            .unwrap();
+        let recording = arcade.recording.clone().unwrap();
+        assert_eq!(recording.frames.len(), arcade.frames + 1);
+        assert_eq!(recording.frames[0].joystick, None);
+        assert_eq!(recording.frames[0].blocks, 253);
+        assert_eq!(recording.frames.last().unwrap().score, 12263);
+
+        let text = recording.to_text();
+        assert!(text.starts_with("quarters 2\nboot 0 253 "));
+        let parsed = Recording::parse(&text).unwrap();
+        assert_eq!(parsed, recording);
+
+        let replayed = replay(&program, &parsed, &Display::Headless).unwrap();
+        assert_eq!(replayed.screen, arcade.screen);
+
+        // a different input changes what gets drawn
+        let mut tampered = parsed.clone();
+        let frame = tampered
+            .frames
+            .iter_mut()
+            .find(|f| f.joystick == Some(Joystick::Left))
+            .unwrap();
+        frame.joystick = Some(Joystick::Right);
+        assert!(replay(&program, &tampered, &Display::Headless).is_err());
+    }
+
+    #[test]
+    fn score_timeline() {
+        let program = parse_input(include_str!("../input.txt"));
+        let mut arcade = Arcade::recorded(&program, Some(2)).unwrap();
+        arcade.play(&mut BallTracking, &Display::Headless).unwrap();
+        let recording = arcade.recording.unwrap();
+        let cleared = recording.cleared_at().unwrap();
+        assert_eq!(recording.frames[cleared].score, 12263);
+        assert!(recording.frames[..cleared].iter().all(|f| f.blocks > 0));
+        let report = recording.report(1000);
+        assert!(report.starts_with("   frame     score  blocks\n       0         0     253\n"));
+        assert!(report.ends_with(&format!("board cleared after {} frames", cleared)));
+    }
+
+    #[test]
+    fn invalid_recordings() {
+        assert!(Recording::parse("").is_err());
+        assert_eq!(
+            Recording::parse("quarters 2\nboot 0 1\n5 0 1\n"),
+            Err(String::from("line 3: invalid joystick input 5"))
+        );
+        assert!(Recording::parse("quarters 2\nboot 0 1 1,2,x\n").is_err());
+        assert_eq!(
+            Recording::parse("\n\nquarters 2\n\nboot 0 1\n\n5 0 1\n"),
+            Err(String::from("line 7: invalid joystick input 5"))
+        );
+        assert_eq!(
+            Recording::parse("\nscore 2\n"),
+            Err(String::from("line 2: expected quarters <n|->"))
+        );
+        let recording = Recording::parse("quarters -\nboot 0 1 1,2,2\n").unwrap();
+        assert_eq!(recording.quarters, None);
+        assert_eq!(recording.frames[0].output, vec![1, 2, 2]);
+    }
+}
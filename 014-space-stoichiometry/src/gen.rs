@@ -0,0 +1,115 @@
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+// like the puzzle input, ORE is only ever the single input of a reaction,
+// the remaining chemicals only consume chemicals generated before them, so
+// the reactions always form a DAG, and every chemical ends up used by FUEL
+pub fn reactions(seed: u64, chemicals: usize, max_inputs: usize, max_quantity: i128) -> String {
+    assert!(
+        chemicals >= 1,
+        "FUEL needs at least one chemical to be made of"
+    );
+    assert!(max_inputs >= 1, "Reactions need at least one input");
+    assert!(max_quantity >= 1, "Quantities have to be at least 1");
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let ore_chemicals = (chemicals / 6).max(1);
+    let mut used_names: HashSet<String> = ["ORE", "FUEL"].iter().map(|n| n.to_string()).collect();
+    let mut names: Vec<String> = Vec::new();
+    let mut consumed = vec![false; chemicals];
+    let mut lines = Vec::new();
+    for i in 0..chemicals + 1 {
+        let name = if i == chemicals {
+            String::from("FUEL")
+        } else {
+            let mut name = random_name(&mut rng);
+            while used_names.contains(&name) {
+                name = random_name(&mut rng);
+            }
+            used_names.insert(name.clone());
+            name
+        };
+
+        let inputs = if i < ore_chemicals {
+            format!("{} ORE", rng.gen_range(1, max_quantity * 10 + 1))
+        } else {
+            let inputs_count = rng.gen_range(1, max_inputs.min(i) + 1);
+            let mut inputs: Vec<usize> = index::sample(&mut rng, i, inputs_count).into_vec();
+            if i == chemicals {
+                for (j, used) in consumed.iter().enumerate() {
+                    if !used && !inputs.contains(&j) {
+                        inputs.push(j);
+                    }
+                }
+            }
+            inputs.sort();
+            inputs
+                .iter()
+                .map(|&j| {
+                    consumed[j] = true;
+                    format!("{} {}", rng.gen_range(1, max_quantity + 1), names[j])
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        lines.push(format!(
+            "{} => {} {}",
+            inputs,
+            rng.gen_range(1, max_quantity + 1),
+            name
+        ));
+        names.push(name);
+    }
+
+    lines.shuffle(&mut rng);
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    contents
+}
+
+fn random_name(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(1, 6);
+    (0..len)
+        .map(|_| (b'A' + rng.gen_range(0, 26)) as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::reactions;
+    use crate::{parse_input, part1, part2};
+
+    #[test]
+    fn same_seed_same_reactions() {
+        assert_eq!(reactions(9, 40, 4, 10), reactions(9, 40, 4, 10));
+        assert_ne!(reactions(9, 40, 4, 10), reactions(10, 40, 4, 10));
+    }
+
+    #[test]
+    fn single_ore_chemical() {
+        let contents = reactions(1, 1, 3, 10);
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let fuel = lines.iter().find(|l| l.ends_with("FUEL")).unwrap();
+        let ore = lines.iter().find(|l| l.contains("ORE")).unwrap();
+        let parse = |s: &str| s.split(' ').next().unwrap().parse::<i128>().unwrap();
+        let needed = parse(fuel);
+        let produced = parse(ore.split(" => ").nth(1).unwrap());
+        let batches = (needed + produced - 1) / produced;
+        assert_eq!(part1(&parse_input(&contents)), batches * parse(ore));
+    }
+
+    #[test]
+    fn every_chemical_is_produced_once() {
+        for seed in 0..20 {
+            let reactions = parse_input(&reactions(seed, 50, 4, 9));
+            assert_eq!(reactions.transformations.len(), 51);
+            assert!(reactions.transformations.contains_key("FUEL"));
+            let ore = part1(&reactions);
+            assert!(ore > 0);
+            assert!(part2(&reactions) >= 1_000_000_000_000 / ore);
+        }
+    }
+}
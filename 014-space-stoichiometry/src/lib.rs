@@ -0,0 +1,240 @@
+pub mod gen;
+
+use regex::Regex;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct Transformation {
+    pub inputs: Vec<Material>,
+    pub output: Material,
+}
+impl Transformation {
+    pub fn new(inputs: Vec<Material>, output: Material) -> Transformation {
+        Transformation { inputs, output }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Material {
+    pub name: String,
+    pub quantity: i128,
+    pub appearance: i128,
+}
+impl Material {
+    pub fn new(name: String, quantity: i128) -> Material {
+        Material {
+            name,
+            quantity,
+            appearance: 0,
+        }
+    }
+}
+
+pub struct Reactions {
+    pub transformations: HashMap<String, Transformation>,
+}
+
+pub fn parse_input(contents: &str) -> Reactions {
+    Reactions {
+        transformations: parse_transformations(contents),
+    }
+}
+
+pub fn part1(reactions: &Reactions) -> i128 {
+    find_min_required_ore(
+        &mut reactions.transformations.clone(),
+        String::from("FUEL"),
+        1,
+    )
+}
+
+pub fn part2(reactions: &Reactions) -> i128 {
+    calculate_max_fuel(&mut reactions.transformations.clone())
+}
+
+pub fn calculate_max_fuel(transformations: &mut HashMap<String, Transformation>) -> i128 {
+    let mut ore = 0;
+    let mut prev;
+    let mut fuel = 1000000;
+    let mut increment = 1000000;
+    let target_ore = 1000000000000;
+    loop {
+        prev = ore;
+        ore = find_min_required_ore(&mut transformations.clone(), String::from("FUEL"), fuel);
+
+        if prev >= target_ore && ore <= target_ore && increment == 1 {
+            break;
+        }
+
+        if ore < target_ore {
+            if ore - prev > prev {
+                increment *= 2;
+            }
+            fuel += increment;
+        } else {
+            increment = (increment as f64 / 2f64).ceil() as i128;
+            fuel -= increment;
+        }
+    }
+
+    fuel
+}
+
+pub fn find_min_required_ore(
+    transformations: &mut HashMap<String, Transformation>,
+    node: String,
+    n: i128,
+) -> i128 {
+    let mut total = 0;
+    let mut requirements: HashMap<String, i128> = HashMap::new();
+    calculate_requirements(transformations, &mut requirements, node, n);
+    for req in requirements.keys() {
+        let t = transformations.get(req).unwrap();
+        let mut required_multiplicator =
+            requirements.get(&t.output.name).unwrap() / t.output.quantity;
+        let remainder = requirements.get(&t.output.name).unwrap() % t.output.quantity;
+        if remainder > 0 {
+            required_multiplicator += 1;
+        }
+        total += t.inputs[0].quantity * required_multiplicator;
+    }
+    total
+}
+
+pub fn calculate_requirements(
+    transformations: &mut HashMap<String, Transformation>,
+    requirements: &mut HashMap<String, i128>,
+    node: String,
+    n: i128,
+) {
+    let transf = transformations.get(&node).unwrap().clone();
+    if transf.inputs.len() > 1 || transf.inputs[0].name != "ORE" {
+        let material = transformations.get_mut(&node).unwrap();
+        if material.output.appearance > 1 {
+            material.output.appearance -= 1;
+            return;
+        }
+        for i in 0..transf.inputs.len() {
+            let quantity = requirements.get(&node).unwrap_or(&n);
+            let mut multiplier = quantity / transf.output.quantity;
+            let extra = quantity % transf.output.quantity;
+            if extra > 0 {
+                multiplier += 1;
+            }
+            let mut value = multiplier * transf.inputs[i].quantity;
+
+            if let Some(val) = requirements.get_mut(&transf.inputs[i].name) {
+                value += *val;
+            }
+            requirements.insert(transf.inputs[i].name.clone(), value);
+        }
+        requirements.remove(&node);
+        for i in 0..transf.inputs.len() {
+            calculate_requirements(
+                transformations,
+                requirements,
+                transf.inputs[i].name.clone(),
+                n,
+            );
+        }
+    }
+}
+
+pub fn parse_transformations(contents: &str) -> HashMap<String, Transformation> {
+    let re = Regex::new(r"(\d+) ([A-Za-z]+)").unwrap();
+    let mut appearance_count: HashMap<String, i128> = HashMap::new();
+    let transformations: Vec<Transformation> = contents
+        .split_terminator('\n')
+        .map(|s| {
+            let mut inputs = Vec::new();
+            let mut output = Material::new("".to_string(), 0);
+            let mut iter = re.captures_iter(s).peekable();
+            while let Some(caps) = iter.next() {
+                if iter.peek().is_none() {
+                    output.name = caps[2].to_string();
+                    output.quantity = caps[1].parse::<i128>().unwrap();
+                } else {
+                    let material =
+                        Material::new(caps[2].to_string(), caps[1].parse::<i128>().unwrap());
+                    let mut value = 1;
+                    if let Some(count) = appearance_count.get(&material.name) {
+                        value += count;
+                    }
+                    appearance_count.insert(material.name.clone(), value);
+                    inputs.push(material);
+                }
+            }
+            Transformation::new(inputs, output)
+        })
+        .collect();
+    let mut map = HashMap::new();
+    for transf in transformations {
+        let mut t = transf.clone();
+        t.output.appearance = *appearance_count.get(&t.output.name).unwrap_or(&0);
+        map.insert(transf.output.name, t);
+    }
+    map
+}
+
+#[cfg(test)]
+mod test {
+    use super::calculate_max_fuel;
+    use super::find_min_required_ore;
+    use super::parse_transformations;
+
+    #[test]
+    fn part1_sample_input1() {
+        let mut map = parse_transformations(include_str!("../test-input.txt"));
+        let required_ores = find_min_required_ore(&mut map, String::from("FUEL"), 1);
+        assert_eq!(required_ores, 31);
+    }
+
+    #[test]
+    fn part1_sample_input2() {
+        let mut map = parse_transformations(include_str!("../test-input2.txt"));
+        let required_ores = find_min_required_ore(&mut map, String::from("FUEL"), 1);
+        assert_eq!(required_ores, 165);
+    }
+
+    #[test]
+    fn part1_sample_input3() {
+        let mut map = parse_transformations(include_str!("../test-input3.txt"));
+        let required_ores = find_min_required_ore(&mut map, String::from("FUEL"), 1);
+        assert_eq!(required_ores, 13312);
+    }
+
+    #[test]
+    fn part1_sample_input4() {
+        let mut map = parse_transformations(include_str!("../test-input4.txt"));
+        let required_ores = find_min_required_ore(&mut map, String::from("FUEL"), 1);
+        assert_eq!(required_ores, 180697);
+    }
+
+    #[test]
+    fn part1_sample_input5() {
+        let mut map = parse_transformations(include_str!("../test-input5.txt"));
+        let required_ores = find_min_required_ore(&mut map, String::from("FUEL"), 1);
+        assert_eq!(required_ores, 2210736);
+    }
+
+    #[test]
+    fn part2_sample_input3() {
+        let mut map = parse_transformations(include_str!("../test-input3.txt"));
+        let max_fueld_amount = calculate_max_fuel(&mut map);
+        assert_eq!(max_fueld_amount, 82892753);
+    }
+
+    #[test]
+    fn part2_sample_input4() {
+        let mut map = parse_transformations(include_str!("../test-input4.txt"));
+        let max_fueld_amount = calculate_max_fuel(&mut map);
+        assert_eq!(max_fueld_amount, 5586022);
+    }
+
+    #[test]
+    fn part2_sample_input5() {
+        let mut map = parse_transformations(include_str!("../test-input5.txt"));
+        let max_fueld_amount = calculate_max_fuel(&mut map);
+        assert_eq!(max_fueld_amount, 460664);
+    }
+}
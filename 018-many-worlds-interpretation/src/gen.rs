@@ -0,0 +1,186 @@
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+
+// builds four perfect mazes of `size`x`size` cells, one per quadrant, which
+// only meet in the open 3x3 area around the entrance, so the map stays valid
+// after it gets split into quadrants for part 2
+pub fn tunnel_map(seed: u64, size: usize, keys: usize, doors: usize) -> String {
+    assert!(size >= 1, "Quadrants need at least one cell");
+    assert!((1..=26).contains(&keys), "There can be 1 to 26 keys");
+    assert!(doors <= keys, "Every door needs its own key");
+    assert!(
+        keys <= 4 * size * size,
+        "Can't fit {} keys in the map",
+        keys
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let quadrant = 2 * size + 1;
+    let width = 2 * quadrant + 1;
+    let center = quadrant;
+    let mut map = vec![vec!['#'; width]; width];
+    let mut cells = Vec::new();
+    for &(qx, qy) in &[
+        (0, 0),
+        (quadrant + 1, 0),
+        (0, quadrant + 1),
+        (quadrant + 1, quadrant + 1),
+    ] {
+        carve_maze(&mut rng, &mut map, qx, qy, size);
+        for i in 0..size {
+            for j in 0..size {
+                cells.push((qx + 2 * i + 1, qy + 2 * j + 1));
+            }
+        }
+    }
+    for row in &mut map[center - 1..=center + 1] {
+        for tile in &mut row[center - 1..=center + 1] {
+            *tile = '.';
+        }
+    }
+    map[center - 1][center - 2] = '.';
+    map[center - 1][center + 2] = '.';
+    map[center + 1][center - 2] = '.';
+    map[center + 1][center + 2] = '.';
+    map[center][center] = '@';
+
+    let key_cells: Vec<(usize, usize)> = index::sample(&mut rng, cells.len(), keys)
+        .iter()
+        .map(|i| cells[i])
+        .collect();
+    for (i, &(x, y)) in key_cells.iter().enumerate() {
+        map[y][x] = (b'a' + i as u8) as char;
+    }
+
+    // keys are meant to be collected in a random order, a door can only go
+    // where everything behind it is collected after its own key
+    let mut order: Vec<usize> = (0..keys).collect();
+    order.shuffle(&mut rng);
+    let mut position = vec![0; keys];
+    for (p, &key) in order.iter().enumerate() {
+        position[key] = p;
+    }
+    let first_key_behind = first_key_behind(&map, center, &position);
+    for &key in order.iter().take(doors) {
+        let candidates: Vec<(usize, usize)> = (0..width)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                map[y][x] == '.'
+                    && (x + 1 < center || x > center + 1 || y + 1 < center || y > center + 1)
+                    && first_key_behind[y][x] > position[key]
+            })
+            .collect();
+        if let Some(&(x, y)) = candidates.choose(&mut rng) {
+            map[y][x] = (b'A' + key as u8) as char;
+        }
+    }
+
+    let mut contents = String::new();
+    for row in map {
+        contents.push_str(&row.iter().collect::<String>());
+        contents.push('\n');
+    }
+    contents
+}
+
+fn carve_maze(rng: &mut StdRng, map: &mut [Vec<char>], qx: usize, qy: usize, size: usize) {
+    let mut visited = vec![vec![false; size]; size];
+    let mut stack = vec![(0, 0)];
+    visited[0][0] = true;
+    map[qy + 1][qx + 1] = '.';
+    while let Some(&(i, j)) = stack.last() {
+        let mut next = Vec::new();
+        if i > 0 && !visited[i - 1][j] {
+            next.push((i - 1, j));
+        }
+        if i + 1 < size && !visited[i + 1][j] {
+            next.push((i + 1, j));
+        }
+        if j > 0 && !visited[i][j - 1] {
+            next.push((i, j - 1));
+        }
+        if j + 1 < size && !visited[i][j + 1] {
+            next.push((i, j + 1));
+        }
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (ni, nj) = next[rng.gen_range(0, next.len())];
+        visited[ni][nj] = true;
+        map[qy + 2 * nj + 1][qx + 2 * ni + 1] = '.';
+        map[qy + nj + j + 1][qx + ni + i + 1] = '.';
+        stack.push((ni, nj));
+    }
+}
+
+// for every tile, the earliest collection position of a key that can only
+// be reached by walking through that tile
+fn first_key_behind(map: &[Vec<char>], center: usize, position: &[usize]) -> Vec<Vec<usize>> {
+    let width = map.len();
+    let mut parent = vec![vec![None; width]; width];
+    let mut seen = vec![vec![false; width]; width];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back((center, center));
+    seen[center][center] = true;
+    while let Some((x, y)) = queue.pop_front() {
+        order.push((x, y));
+        for &(nx, ny) in &[(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+            if map[ny][nx] != '#' && !seen[ny][nx] {
+                seen[ny][nx] = true;
+                parent[ny][nx] = Some((x, y));
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    let mut first = vec![vec![usize::MAX; width]; width];
+    for &(x, y) in order.iter().rev() {
+        if map[y][x].is_ascii_lowercase() {
+            let key = (map[y][x] as u8 - b'a') as usize;
+            first[y][x] = first[y][x].min(position[key]);
+        }
+        if let Some((px, py)) = parent[y][x] {
+            first[py][px] = first[py][px].min(first[y][x]);
+        }
+    }
+    first
+}
+
+#[cfg(test)]
+mod test {
+    use super::tunnel_map;
+    use crate::{
+        locate_start_pos_and_all_keys_and_doors, parse_input, part1, part2, split_into_quadrants,
+    };
+
+    #[test]
+    fn same_seed_same_map() {
+        assert_eq!(tunnel_map(2, 6, 10, 5), tunnel_map(2, 6, 10, 5));
+        assert_ne!(tunnel_map(2, 6, 10, 5), tunnel_map(3, 6, 10, 5));
+    }
+
+    #[test]
+    fn map_splits_into_four_robots() {
+        let mut map = parse_input(&tunnel_map(1, 5, 8, 4)).grid;
+        assert_eq!(map.len(), 23);
+        let (_, keys, doors) = locate_start_pos_and_all_keys_and_doors(&mut map);
+        assert_eq!(keys.len(), 8);
+        assert!(doors.len() <= 4);
+        split_into_quadrants(&mut map).unwrap();
+        let (robots, _, _) = locate_start_pos_and_all_keys_and_doors(&mut map);
+        assert_eq!(robots.len(), 4);
+    }
+
+    #[test]
+    fn generated_maps_are_solvable() {
+        for seed in 0..10 {
+            let tunnel_map = parse_input(&tunnel_map(seed, 4, 6, 6));
+            assert!(part1(&tunnel_map) > 0);
+            assert!(part2(&tunnel_map) > 0);
+        }
+    }
+}
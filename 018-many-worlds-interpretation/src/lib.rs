@@ -0,0 +1,511 @@
+pub mod gen;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::BinaryHeap;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Node {
+    pub name: char,
+    pub x: i64,
+    pub y: i64,
+}
+impl Node {
+    pub fn new(name: char, x: i64, y: i64) -> Node {
+        Node { name, x, y }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Distance {
+    pub value: i64,
+    pub doors: i64,
+}
+impl Distance {
+    pub fn new(distance: i64) -> Distance {
+        Distance {
+            value: distance,
+            doors: 0,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+struct State {
+    robots: Vec<Node>,
+    remaining_keys: i64,
+    cost: i64,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &State) -> Ordering {
+        other.cost.cmp(&self.cost)
+        // // .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &State) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct TunnelMap {
+    pub grid: Vec<Vec<char>>,
+}
+
+pub fn parse_input(contents: &str) -> TunnelMap {
+    TunnelMap {
+        grid: parse_map(contents),
+    }
+}
+
+pub fn part1(tunnel_map: &TunnelMap) -> i64 {
+    get_collect_keys_shortest_path(&mut tunnel_map.grid.clone())
+}
+
+pub fn part2(tunnel_map: &TunnelMap) -> i64 {
+    let mut map = tunnel_map.grid.clone();
+    split_into_quadrants(&mut map).expect("Cannot split the map into quadrants");
+    get_collect_keys_shortest_path2(&mut map)
+}
+
+// replaces the single entrance and its surroundings with four separate
+// entrances, leaving maps which already have several robots untouched, and
+// fails without changing the map when the entrance is on its border
+pub fn split_into_quadrants(map: &mut [Vec<char>]) -> Result<(), String> {
+    let (robots, _, _) = locate_start_pos_and_all_keys_and_doors(map);
+    if robots.len() != 1 {
+        return Ok(());
+    }
+    let x = robots[0].x as usize;
+    let y = robots[0].y as usize;
+    let fits =
+        x >= 1 && y >= 1 && x + 1 < map.len() && (x - 1..=x + 1).all(|i| y + 1 < map[i].len());
+    if !fits {
+        return Err(format!("The entrance at ({}, {}) is on the border", x, y));
+    }
+    for row in &mut map[x - 1..=x + 1] {
+        for cell in &mut row[y - 1..=y + 1] {
+            *cell = '#';
+        }
+    }
+    for (i, j) in [
+        (x - 1, y - 1),
+        (x - 1, y + 1),
+        (x + 1, y - 1),
+        (x + 1, y + 1),
+    ] {
+        map[i][j] = '@';
+    }
+    Ok(())
+}
+
+pub fn get_collect_keys_shortest_path2(map: &mut [Vec<char>]) -> i64 {
+    let (robots, all_keys, _) = locate_start_pos_and_all_keys_and_doors(map);
+    let distances = init_distance_map(map, &all_keys, &robots);
+    println!("distances\n");
+    print_distances(&distances, robots.clone());
+
+    let remaining_keys = (1 << all_keys.len()) - 1;
+
+    let mut seen: HashSet<(String, i64)> = HashSet::new();
+    let mut heap: BinaryHeap<State> = BinaryHeap::new();
+    heap.push(State {
+        robots,
+        remaining_keys,
+        cost: 0,
+    });
+
+    while let Some(State {
+        robots,
+        remaining_keys,
+        cost,
+    }) = heap.pop()
+    {
+        let mut new_remaining_keys = remaining_keys;
+
+        let mut curr_pos = String::new();
+        for robot in robots.iter() {
+            let curr_node_name = robot.name;
+            curr_pos.push(curr_node_name);
+            if curr_node_name.is_ascii_lowercase()
+                && has_bit(new_remaining_keys, curr_node_name as u8 - b'a')
+            {
+                new_remaining_keys = unset_bit(new_remaining_keys, curr_node_name as u8 - b'a');
+            }
+        }
+        if seen.contains(&(curr_pos.clone(), new_remaining_keys)) {
+            continue;
+        }
+        seen.insert((curr_pos, new_remaining_keys));
+
+        if new_remaining_keys == 0 {
+            return cost;
+        }
+
+        for i in 0..robots.len() {
+            let curr_node = robots[i].clone();
+            let curr_node_name = curr_node.name;
+
+            // for each unvisited and unlocked
+            let mut neighbours = all_keys.clone();
+            neighbours.retain(|k| {
+                let distance_between = distances.get(&(curr_node_name, k.name));
+                has_bit(new_remaining_keys, k.name as u8 - b'a')
+                    && distance_between.is_some()
+                    && distance_between.unwrap().doors | !new_remaining_keys == !new_remaining_keys
+            });
+            for v in neighbours.iter() {
+                let mut new_robots = robots.clone();
+                new_robots[i] = v.clone();
+                heap.push(State {
+                    robots: new_robots,
+                    remaining_keys: new_remaining_keys,
+                    cost: cost + distances.get(&(curr_node_name, v.name)).unwrap().value,
+                });
+            }
+        }
+    }
+
+    i64::MAX
+}
+
+fn unset_bit(keys: i64, i: u8) -> i64 {
+    keys & !(1 << i)
+}
+
+fn set_bit(keys: i64, i: u8) -> i64 {
+    keys | (1 << i)
+}
+
+fn has_bit(keys: i64, i: u8) -> bool {
+    keys & (1 << i) == (1 << i)
+}
+
+pub fn get_collect_keys_shortest_path(map: &mut [Vec<char>]) -> i64 {
+    let (robots, all_keys, _) = locate_start_pos_and_all_keys_and_doors(map);
+    let start_pos = robots[0].clone();
+
+    let distances = init_distance_map(map, &all_keys, &robots);
+    println!("distances\n");
+    print_distances(&distances, robots.clone());
+
+    let mask = 0;
+    let mut dp: HashMap<(i64, Node), i64> = HashMap::new();
+
+    tsp(mask, start_pos.clone(), all_keys, distances, &mut dp)
+}
+
+pub fn tsp(
+    mask: i64,
+    curr_node: Node,
+    all_keys: HashSet<Node>,
+    distances: HashMap<(char, char), Distance>,
+    dp: &mut HashMap<(i64, Node), i64>,
+) -> i64 {
+    if let Some(value) = dp.get(&(mask, curr_node.clone())) {
+        return *value;
+    }
+    let curr_node_name = curr_node.clone().name;
+
+    let mut ans = i64::MAX;
+
+    // for each unvisited and unlocked
+    let mut neighbours = all_keys.clone();
+    neighbours.retain(|k| {
+        let distance_between = distances.get(&(curr_node_name, k.name));
+        !has_bit(mask, k.name as u8 - b'a')
+            && distance_between.is_some()
+            && distance_between.unwrap().doors | mask == mask
+    });
+    if neighbours.is_empty() {
+        return 0;
+    }
+    for v in neighbours.iter() {
+        let city = v.name as u8 - b'a';
+        if mask & (1 << city) == 0 {
+            let best_dist = tsp(
+                mask | (1 << city),
+                v.clone(),
+                all_keys.clone(),
+                distances.clone(),
+                dp,
+            );
+            let new_ans = distances.get(&(curr_node_name, v.name)).unwrap().value + best_dist;
+            if ans > new_ans {
+                ans = new_ans;
+            }
+        }
+    }
+    dp.insert((mask, curr_node), ans);
+    ans
+}
+
+pub fn init_distance_map(
+    map: &mut [Vec<char>],
+    keys: &HashSet<Node>,
+    robots: &Vec<Node>,
+) -> HashMap<(char, char), Distance> {
+    let mut distances = HashMap::new();
+    let mut keys_with_start = keys.clone();
+    for robot in robots {
+        keys_with_start.insert(robot.clone());
+    }
+    let keys_clone = keys_with_start.clone();
+
+    for key in keys_with_start {
+        find_distances_from(map, &mut distances, &keys_clone, &key);
+    }
+
+    distances
+}
+
+pub fn find_distances_from(
+    map: &mut [Vec<char>],
+    distances: &mut HashMap<(char, char), Distance>,
+    keys: &HashSet<Node>,
+    from: &Node,
+) {
+    let mut queue = vec![from.clone()];
+    let mut remaining_keys = keys.clone();
+    let mut visited = HashSet::new();
+    let mut parents: HashMap<Node, Node> = HashMap::new();
+    visited.insert((from.x, from.y));
+
+    while !queue.is_empty() && !remaining_keys.is_empty() {
+        let curr_pos = queue.remove(0);
+        if remaining_keys.contains(&curr_pos) {
+            remaining_keys.remove(&curr_pos);
+            if let Entry::Vacant(entry) = distances.entry((from.name, curr_pos.name)) {
+                let mut curr_el = curr_pos.clone();
+                let mut distance = Distance::new(0);
+                while let Some(parent) = parents.get(&curr_el) {
+                    distance.value += 1;
+                    if curr_el.name.is_ascii_uppercase() {
+                        distance.doors = set_bit(distance.doors, curr_el.name as u8 - b'A');
+                    }
+                    curr_el = parent.clone();
+                }
+                entry.insert(distance);
+            }
+        }
+        for pos in get_available_next_positions(map, &curr_pos) {
+            if !visited.contains(&(pos.x, pos.y)) {
+                let new_pos = pos.clone();
+                visited.insert((pos.x, pos.y));
+                parents.insert(pos, curr_pos.clone());
+
+                queue.push(new_pos);
+            }
+        }
+    }
+}
+
+fn get_available_next_positions(map: &mut [Vec<char>], curr_pos: &Node) -> Vec<Node> {
+    let mut next_positions = Vec::new();
+    let mut next_pos = curr_pos.clone();
+    next_pos.x -= 1;
+    if next_pos.x >= 0 && get_value(map, &next_pos) != '#' {
+        next_pos.name = get_value(map, &next_pos);
+        next_positions.push(next_pos);
+    }
+    next_pos = curr_pos.clone();
+    next_pos.x += 1;
+    if next_pos.x < map.len() as i64 && get_value(map, &next_pos) != '#' {
+        next_pos.name = get_value(map, &next_pos);
+        next_positions.push(next_pos);
+    }
+    next_pos = curr_pos.clone();
+    next_pos.y -= 1;
+    if next_pos.y >= 0 && get_value(map, &next_pos) != '#' {
+        next_pos.name = get_value(map, &next_pos);
+        next_positions.push(next_pos);
+    }
+    next_pos = curr_pos.clone();
+    next_pos.y += 1;
+    if next_pos.y < map[0].len() as i64 && get_value(map, &next_pos) != '#' {
+        next_pos.name = get_value(map, &next_pos);
+        next_positions.push(next_pos);
+    }
+
+    next_positions
+}
+
+fn get_value(map: &mut [Vec<char>], curr_pos: &Node) -> char {
+    map[curr_pos.x as usize][curr_pos.y as usize]
+}
+
+pub fn locate_start_pos_and_all_keys_and_doors(
+    map: &mut [Vec<char>],
+) -> (Vec<Node>, HashSet<Node>, HashSet<Node>) {
+    let mut robot_count = 1;
+    let mut robots = Vec::new();
+    let mut keys = HashSet::new();
+    let mut doors = HashSet::new();
+    for (i, row) in map.iter().enumerate() {
+        for (j, &curr_char) in row.iter().enumerate() {
+            if curr_char == '@' {
+                let start_pos = Node::new(
+                    std::char::from_digit(robot_count, 10).unwrap(),
+                    i as i64,
+                    j as i64,
+                );
+                robots.push(start_pos);
+                robot_count += 1;
+            } else if curr_char.is_ascii_lowercase() {
+                keys.insert(Node::new(curr_char, i as i64, j as i64));
+            } else if curr_char.is_ascii_uppercase() {
+                doors.insert(Node::new(curr_char, i as i64, j as i64));
+            }
+        }
+    }
+
+    (robots, keys, doors)
+}
+
+pub fn parse_map(contents: &str) -> Vec<Vec<char>> {
+    let asteroid_map: Vec<Vec<char>> = contents
+        .split_terminator('\n')
+        .map(|r| {
+            let chars = r.chars();
+            let mut row = Vec::new();
+            for ch in chars {
+                row.push(ch);
+            }
+            row
+        })
+        .collect();
+    asteroid_map
+}
+
+pub fn print_map(map: &mut [Vec<char>]) {
+    // print!("{}[2J", 27 as char);
+    let w = map.len();
+    let h = map[0].len();
+    let mut sb = String::new();
+    for row in map.iter().take(w) {
+        for &cell in row.iter().take(h) {
+            sb.push(cell);
+        }
+        sb.push('\n');
+    }
+    println!("{}", sb)
+}
+
+pub fn print_distances(map: &HashMap<(char, char), Distance>, robots: Vec<Node>) {
+    let mut string = String::new();
+    string.push_str(format!("{:4}", ' ').as_str());
+    let mut keys: Vec<char> = (0..26).map(|x| (b'a' + x) as char).collect();
+    for (i, robot) in robots.iter().enumerate() {
+        keys.insert(i, robot.name);
+    }
+    for i in keys.clone() {
+        string.push_str(format!("{:4}", i).as_str());
+    }
+    string.push('\n');
+    for i in 0..keys.len() {
+        string.push_str(format!("{:4}", keys[i]).as_str());
+        for j in 0..keys.len() {
+            if let Some(dist) = map.get(&(keys[i], keys[j])) {
+                string.push_str(format!("{:4}", dist.value).as_str());
+            } else {
+                string.push_str(format!("{:4}", "-").as_str())
+            }
+        }
+        string.push('\n');
+    }
+    println!("{}", string);
+}
+
+#[cfg(test)]
+mod test {
+    use super::get_collect_keys_shortest_path2;
+    use super::parse_map;
+    use super::split_into_quadrants;
+
+    #[test]
+    fn part1_sample_input1() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input1.txt"));
+        assert_eq!(get_collect_keys_shortest_path2(&mut map_of_the_tunnels), 8);
+    }
+
+    #[test]
+    fn part1_sample_input2() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input2.txt"));
+        assert_eq!(get_collect_keys_shortest_path2(&mut map_of_the_tunnels), 86);
+    }
+
+    #[test]
+    fn part1_sample_input3() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input3.txt"));
+        assert_eq!(
+            get_collect_keys_shortest_path2(&mut map_of_the_tunnels),
+            132
+        );
+    }
+
+    #[test]
+    fn part1_sample_input4() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input4.txt"));
+        assert_eq!(
+            get_collect_keys_shortest_path2(&mut map_of_the_tunnels),
+            136
+        );
+    }
+
+    #[test]
+    fn part1_sample_input5() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input5.txt"));
+        assert_eq!(get_collect_keys_shortest_path2(&mut map_of_the_tunnels), 81);
+    }
+
+    #[test]
+    fn part2_sample_input1() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input6.txt"));
+        let steps_count = get_collect_keys_shortest_path2(&mut map_of_the_tunnels);
+        assert_eq!(steps_count, 8);
+    }
+
+    #[test]
+    fn part2_sample_input2() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input7.txt"));
+        let steps_count = get_collect_keys_shortest_path2(&mut map_of_the_tunnels);
+        assert_eq!(steps_count, 24);
+    }
+
+    #[test]
+    fn part2_sample_input3() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input8.txt"));
+        let steps_count = get_collect_keys_shortest_path2(&mut map_of_the_tunnels);
+        assert_eq!(steps_count, 32);
+    }
+
+    #[test]
+    fn part2_sample_input4() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../test-input9.txt"));
+        let steps_count = get_collect_keys_shortest_path2(&mut map_of_the_tunnels);
+        assert_eq!(steps_count, 72);
+    }
+
+    #[test]
+    fn part2_split_into_quadrants() {
+        let mut map_of_the_tunnels = parse_map(include_str!("../input.txt"));
+        split_into_quadrants(&mut map_of_the_tunnels).unwrap();
+        assert_eq!(
+            map_of_the_tunnels,
+            parse_map(include_str!("../input-pt2.txt"))
+        );
+    }
+
+    #[test]
+    fn entrance_on_the_border() {
+        for contents in &["@.a\n...", "#.#\n#.@\n###", "#a#\n#@#"] {
+            let mut map = parse_map(contents);
+            let original = map.clone();
+            assert!(split_into_quadrants(&mut map).is_err());
+            assert_eq!(map, original);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+// the maze is a ring of `width`x`height` cells, `thickness` cells wide, with
+// every portal pair joining the outer edge to the inner one like the puzzle
+// input does, AA and ZZ are both placed on the outer edge
+pub fn donut_maze(
+    seed: u64,
+    width: usize,
+    height: usize,
+    thickness: usize,
+    portals: usize,
+) -> String {
+    assert!(
+        thickness >= 1,
+        "The ring needs to be at least one cell thick"
+    );
+    assert!(
+        width >= 2 * thickness + 3 && height >= 2 * thickness + 3,
+        "The hole needs to be at least 3 cells wide to fit the labels"
+    );
+    // two letter labels other than AA and ZZ
+    assert!(
+        portals <= 26 * 26 - 2,
+        "There are only {} portal labels",
+        26 * 26 - 2
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let maze_width = 2 * width + 1;
+    let maze_height = 2 * height + 1;
+    let mut map = vec![vec![' '; maze_width + 4]; maze_height + 4];
+    for y in 0..maze_height {
+        for x in 0..maze_width {
+            map[y + 2][x + 2] = '#';
+        }
+    }
+    let in_ring = |i: usize, j: usize| {
+        i < thickness || i >= width - thickness || j < thickness || j >= height - thickness
+    };
+    for y in 2 * thickness + 1..2 * (height - thickness) {
+        for x in 2 * thickness + 1..2 * (width - thickness) {
+            map[y + 2][x + 2] = ' ';
+        }
+    }
+    carve_ring(&mut rng, &mut map, width, height, &in_ring);
+
+    // (x, y) of each opening, together with the direction its label goes in
+    let mut outer = Vec::new();
+    for x in (1..maze_width).step_by(2) {
+        outer.push((x, 0, (0, -1)));
+        outer.push((x, maze_height - 1, (0, 1)));
+    }
+    for y in (1..maze_height).step_by(2) {
+        outer.push((0, y, (-1, 0)));
+        outer.push((maze_width - 1, y, (1, 0)));
+    }
+    let (left, right) = (2 * thickness, 2 * (width - thickness));
+    let (top, bottom) = (2 * thickness, 2 * (height - thickness));
+    let mut inner = Vec::new();
+    for x in (left + 3..right - 2).step_by(2) {
+        inner.push((x, top, (0, 1)));
+        inner.push((x, bottom, (0, -1)));
+    }
+    for y in (top + 3..bottom - 2).step_by(2) {
+        inner.push((left, y, (1, 0)));
+        inner.push((right, y, (-1, 0)));
+    }
+    assert!(
+        portals <= inner.len() && portals + 2 <= outer.len(),
+        "Can't fit {} portals in the maze",
+        portals
+    );
+
+    let mut names = HashSet::new();
+    let mut labels = vec![String::from("AA"), String::from("ZZ")];
+    while labels.len() < portals + 2 {
+        let name: String = (0..2)
+            .map(|_| (b'A' + rng.gen_range(0, 26)) as char)
+            .collect();
+        if name != "AA" && name != "ZZ" && names.insert(name.clone()) {
+            labels.push(name);
+        }
+    }
+    let outer_openings: Vec<usize> = index::sample(&mut rng, outer.len(), portals + 2).into_vec();
+    let inner_openings: Vec<usize> = index::sample(&mut rng, inner.len(), portals).into_vec();
+    for (i, label) in labels.iter().enumerate() {
+        place_label(&mut map, outer[outer_openings[i]], label);
+        if i >= 2 {
+            place_label(&mut map, inner[inner_openings[i - 2]], label);
+        }
+    }
+
+    let mut contents = String::new();
+    for row in map {
+        contents.push_str(&row.iter().collect::<String>());
+        contents.push('\n');
+    }
+    contents
+}
+
+fn carve_ring<F: Fn(usize, usize) -> bool>(
+    rng: &mut StdRng,
+    map: &mut [Vec<char>],
+    width: usize,
+    height: usize,
+    in_ring: &F,
+) {
+    let mut visited = vec![vec![false; height]; width];
+    let mut stack = vec![(0, 0)];
+    visited[0][0] = true;
+    map[3][3] = '.';
+    while let Some(&(i, j)) = stack.last() {
+        let mut next = Vec::new();
+        if i > 0 {
+            next.push((i - 1, j));
+        }
+        if i + 1 < width {
+            next.push((i + 1, j));
+        }
+        if j > 0 {
+            next.push((i, j - 1));
+        }
+        if j + 1 < height {
+            next.push((i, j + 1));
+        }
+        next.retain(|&(ni, nj)| in_ring(ni, nj) && !visited[ni][nj]);
+        if let Some(&(ni, nj)) = next.choose(rng) {
+            visited[ni][nj] = true;
+            map[2 * nj + 3][2 * ni + 3] = '.';
+            map[nj + j + 3][ni + i + 3] = '.';
+            stack.push((ni, nj));
+        } else {
+            stack.pop();
+        }
+    }
+}
+
+// labels read left to right or top to bottom, whichever side they're on
+fn place_label(map: &mut [Vec<char>], opening: (usize, usize, (i64, i64)), label: &str) {
+    let (x, y, (dx, dy)) = opening;
+    let (x, y) = (x as i64 + 2, y as i64 + 2);
+    map[y as usize][x as usize] = '.';
+    let letters: Vec<char> = label.chars().collect();
+    let (near, far) = if dx + dy > 0 {
+        (letters[0], letters[1])
+    } else {
+        (letters[1], letters[0])
+    };
+    map[(y + dy) as usize][(x + dx) as usize] = near;
+    map[(y + 2 * dy) as usize][(x + 2 * dx) as usize] = far;
+}
+
+#[cfg(test)]
+mod test {
+    use super::donut_maze;
+    use crate::{parse_input, part1, part2};
+
+    #[test]
+    fn same_seed_same_maze() {
+        assert_eq!(donut_maze(4, 12, 10, 3, 6), donut_maze(4, 12, 10, 3, 6));
+        assert_ne!(donut_maze(4, 12, 10, 3, 6), donut_maze(5, 12, 10, 3, 6));
+    }
+
+    #[test]
+    #[should_panic(expected = "There are only 674 portal labels")]
+    fn more_portals_than_labels() {
+        donut_maze(0, 800, 800, 1, 1000);
+    }
+
+    #[test]
+    fn every_portal_has_two_ends() {
+        let maze = parse_input(&donut_maze(1, 15, 12, 4, 8));
+        assert_eq!(maze.portals_by_name.len(), 10);
+        for (name, positions) in maze.portals_by_name.iter() {
+            if name == "AA" || name == "ZZ" {
+                assert_eq!(positions.len(), 1);
+            } else {
+                assert_eq!(positions.len(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn generated_mazes_are_solvable() {
+        for seed in 0..10 {
+            let maze = parse_input(&donut_maze(seed, 10, 9, 3, 5));
+            assert!(part2(&maze) >= part1(&maze));
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// both deck sizes used by the puzzle are prime, so any increment between 2
+// and the smaller deck size keeps the shuffle reversible
+pub fn shuffle_list(seed: u64, steps: usize, max_increment: i64, max_cut: i64) -> String {
+    assert!(
+        (2..10007).contains(&max_increment),
+        "Increments have to be between 2 and 10006"
+    );
+    assert!(
+        (1..10007).contains(&max_cut),
+        "Cuts have to be between 1 and 10006"
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut contents = String::new();
+    for _ in 0..steps {
+        let line = match rng.gen_range(0, 3) {
+            0 => String::from("deal into new stack"),
+            1 => {
+                let mut n = rng.gen_range(1, max_cut + 1);
+                if rng.gen() {
+                    n = -n;
+                }
+                format!("cut {}", n)
+            }
+            _ => format!(
+                "deal with increment {}",
+                rng.gen_range(2, max_increment + 1)
+            ),
+        };
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+    contents
+}
+
+#[cfg(test)]
+mod test {
+    use super::shuffle_list;
+    use crate::{find_position, parse_input, part1};
+
+    #[test]
+    fn same_seed_same_list() {
+        assert_eq!(
+            shuffle_list(11, 100, 75, 9999),
+            shuffle_list(11, 100, 75, 9999)
+        );
+        assert_ne!(
+            shuffle_list(11, 100, 75, 9999),
+            shuffle_list(12, 100, 75, 9999)
+        );
+    }
+
+    #[test]
+    fn deck_shuffle_matches_position_tracking() {
+        for seed in 0..20 {
+            let shuffle_list = parse_input(&shuffle_list(seed, 100, 75, 9999));
+            assert_eq!(shuffle_list.steps.len(), 100);
+            assert_eq!(
+                part1(&shuffle_list) as i64,
+                find_position(10007, 2019, &shuffle_list.steps, 1)
+            );
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::hint::black_box;
+use std::io;
+use std::io::prelude::*;
+use std::time::{Duration, Instant};
+
+pub struct Stage {
+    pub name: String,
+    pub times: Vec<Duration>,
+}
+impl Stage {
+    pub fn min(&self) -> Duration {
+        *self.times.iter().min().unwrap()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut sorted = self.times.clone();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted[mid]
+        } else {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        *self.times.iter().max().unwrap()
+    }
+}
+
+pub struct Bench {
+    pub input: String,
+    pub iterations: usize,
+    pub baseline: Option<String>,
+    pub save: Option<String>,
+    pub stages: Vec<Stage>,
+}
+impl Bench {
+    // bench [iterations] [--input <path>] [--baseline <file>] [--save <file>]
+    pub fn from_args(args: &[String]) -> Bench {
+        let mut bench = Bench {
+            input: input_source(None),
+            iterations: 10,
+            baseline: None,
+            save: None,
+            stages: Vec::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("Missing value for {}", arg))
+                    .to_string()
+            };
+            match arg.as_str() {
+                "--input" => bench.input = value(),
+                "--baseline" => bench.baseline = Some(value()),
+                "--save" => bench.save = Some(value()),
+                _ => {
+                    bench.iterations = arg
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid bench argument {}", arg));
+                }
+            }
+        }
+        assert!(bench.iterations > 0, "Need at least one iteration");
+        bench
+    }
+
+    pub fn time<R, F: FnMut() -> R>(&mut self, name: &str, mut f: F) {
+        let mut times = Vec::with_capacity(self.iterations);
+        for _ in 0..self.iterations {
+            let start = Instant::now();
+            black_box(f());
+            times.push(start.elapsed());
+        }
+        self.stages.push(Stage {
+            name: name.to_string(),
+            times,
+        });
+    }
+
+    pub fn summary(&self, baseline: &HashMap<String, Duration>) -> String {
+        let mut res = format!("{:<8}{:>14}{:>14}{:>14}", "stage", "min", "median", "max");
+        if !baseline.is_empty() {
+            res.push_str(&format!("{:>14}", "vs baseline"));
+        }
+        for stage in &self.stages {
+            res.push_str(&format!(
+                "\n{:<8}{:>14}{:>14}{:>14}",
+                stage.name,
+                format!("{:.3?}", stage.min()),
+                format!("{:.3?}", stage.median()),
+                format!("{:.3?}", stage.max())
+            ));
+            if let Some(old) = baseline.get(&stage.name) {
+                let change = (stage.median().as_secs_f64() / old.as_secs_f64() - 1.0) * 100.0;
+                res.push_str(&format!("{:>+13.1}%", change));
+            }
+        }
+        res
+    }
+
+    pub fn finish(&self) {
+        let baseline = match &self.baseline {
+            Some(path) => parse_baseline(&read_file(path)),
+            None => HashMap::new(),
+        };
+        println!("{} iterations", self.iterations);
+        println!("{}", self.summary(&baseline));
+        if let Some(path) = &self.save {
+            let mut file = File::create(path).expect("Failed to create baseline file");
+            file.write_all(self.to_baseline().as_bytes())
+                .expect("Failed to write baseline file");
+        }
+    }
+
+    // one line per stage with the median in nanoseconds
+    pub fn to_baseline(&self) -> String {
+        let mut res = String::new();
+        for stage in &self.stages {
+            res.push_str(&format!("{} {}\n", stage.name, stage.median().as_nanos()));
+        }
+        res
+    }
+}
+
+pub fn parse_baseline(contents: &str) -> HashMap<String, Duration> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let parts: Vec<&str> = l.split_whitespace().collect();
+            let nanos: u64 = parts[1].parse().expect("Invalid baseline file");
+            (parts[0].to_string(), Duration::from_nanos(nanos))
+        })
+        .collect()
+}
+
+// the input path given on the command line, else AOC_INPUT, else input.txt
+pub fn input_source(arg: Option<String>) -> String {
+    arg.or_else(|| env::var("AOC_INPUT").ok())
+        .unwrap_or_else(|| String::from("input.txt"))
+}
+
+// reads the puzzle input from a file, or from stdin when the source is "-"
+pub fn read_input(source: &str) -> String {
+    let mut contents = String::new();
+    if source == "-" {
+        io::stdin()
+            .read_to_string(&mut contents)
+            .expect("Failed to read input from stdin");
+    } else {
+        let mut file = File::open(source).expect("File not found");
+        file.read_to_string(&mut contents)
+            .expect("Failed to read input file");
+    }
+    contents
+}
+
+// the numeric generator argument at `index`, or the default when missing
+pub fn gen_arg(args: &[String], index: usize, default: usize) -> usize {
+    args.get(index)
+        .map(|arg| arg.parse().expect("Invalid gen argument"))
+        .unwrap_or(default)
+}
+
+// removes the option and its values from the arguments
+pub fn take_option(args: &mut Vec<String>, name: &str, values: usize) -> Option<Vec<String>> {
+    let i = args.iter().position(|a| a == name)?;
+    if args.len() < i + 1 + values {
+        panic!("{} takes {} value(s)", name, values);
+    }
+    let option: Vec<String> = args.drain(i..i + 1 + values).skip(1).collect();
+    Some(option)
+}
+
+fn read_file(path: &str) -> String {
+    let mut contents = String::new();
+    let mut file = File::open(path).expect("Baseline file not found");
+    file.read_to_string(&mut contents)
+        .expect("Failed to read baseline file");
+    contents
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_baseline, take_option, Bench, Stage};
+    use std::time::Duration;
+
+    fn stage(millis: Vec<u64>) -> Stage {
+        Stage {
+            name: String::from("part1"),
+            times: millis.into_iter().map(Duration::from_millis).collect(),
+        }
+    }
+
+    #[test]
+    fn min_median_max() {
+        let odd = stage(vec![5, 1, 9, 3, 7]);
+        assert_eq!(odd.min(), Duration::from_millis(1));
+        assert_eq!(odd.median(), Duration::from_millis(5));
+        assert_eq!(odd.max(), Duration::from_millis(9));
+        assert_eq!(stage(vec![4, 1, 2, 8]).median(), Duration::from_millis(3));
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let args: Vec<String> = vec!["25", "--input", "x.txt", "--save", "base.txt"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let bench = Bench::from_args(&args);
+        assert_eq!(bench.iterations, 25);
+        assert_eq!(bench.input, "x.txt");
+        assert_eq!(bench.save, Some(String::from("base.txt")));
+        assert_eq!(bench.baseline, None);
+    }
+
+    #[test]
+    fn options_are_removed() {
+        let mut args: Vec<String> = vec!["input.txt", "--size", "25", "6", "--show"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            take_option(&mut args, "--size", 2),
+            Some(vec![String::from("25"), String::from("6")])
+        );
+        assert_eq!(take_option(&mut args, "--show", 0), Some(vec![]));
+        assert_eq!(take_option(&mut args, "--size", 2), None);
+        assert_eq!(args, vec![String::from("input.txt")]);
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut bench = Bench::from_args(&[String::from("3")]);
+        bench.time("parse", || 1 + 1);
+        bench.stages.push(stage(vec![2, 4, 6]));
+        let baseline = parse_baseline(&bench.to_baseline());
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline["part1"], Duration::from_millis(4));
+        assert_eq!(bench.stages[0].times.len(), 3);
+        assert!(bench.summary(&baseline).contains("vs baseline"));
+    }
+}